@@ -0,0 +1,55 @@
+// ERC-20 allowance and balance reads for CryptoQuest Arbitrage Bot
+use ethabi::Token;
+use web3::types::{Address, Bytes, CallRequest, U256};
+
+use crate::pools::encode_call;
+use crate::ArbitrageError;
+
+// Gas limit for approve(); comfortably above what standard ERC-20s use
+pub const APPROVE_GAS_LIMIT: u64 = 60_000;
+
+// Gas limit for WETH-style deposit() and withdraw(uint256)
+pub const WRAP_GAS_LIMIT: u64 = 50_000;
+
+pub(crate) fn approve_calldata(spender: Address, amount: U256) -> Vec<u8> {
+    encode_call("approve(address,uint256)", &[Token::Address(spender), Token::Uint(amount)])
+}
+
+pub(crate) fn deposit_calldata() -> Vec<u8> {
+    encode_call("deposit()", &[])
+}
+
+pub(crate) fn withdraw_calldata(amount: U256) -> Vec<u8> {
+    encode_call("withdraw(uint256)", &[Token::Uint(amount)])
+}
+
+pub(crate) async fn read_allowance(rpc_url: &str, token: Address, owner: Address, spender: Address) -> Result<U256, ArbitrageError> {
+    // allowance(owner, spender) through eth_call at the latest block
+    let transport = web3::transports::Http::new(rpc_url)?;
+    let call = CallRequest {
+        to: Some(token),
+        data: Some(Bytes(encode_call("allowance(address,address)", &[Token::Address(owner), Token::Address(spender)]))),
+        ..Default::default()
+    };
+    let output = web3::Web3::new(transport).eth().call(call, None).await?;
+
+    if output.0.len() < 32 {
+        return Err(ArbitrageError::Rpc(format!("allowance call on {:?} returned {} bytes", token, output.0.len())));
+    }
+    Ok(U256::from_big_endian(&output.0[..32]))
+}
+
+pub(crate) async fn read_balance(rpc_url: &str, token: Address, owner: Address) -> Result<U256, ArbitrageError> {
+    let transport = web3::transports::Http::new(rpc_url)?;
+    let call = CallRequest {
+        to: Some(token),
+        data: Some(Bytes(encode_call("balanceOf(address)", &[Token::Address(owner)]))),
+        ..Default::default()
+    };
+    let output = web3::Web3::new(transport).eth().call(call, None).await?;
+
+    if output.0.len() < 32 {
+        return Err(ArbitrageError::Rpc(format!("balanceOf call on {:?} returned {} bytes", token, output.0.len())));
+    }
+    Ok(U256::from_big_endian(&output.0[..32]))
+}
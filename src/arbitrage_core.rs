@@ -1,11 +1,109 @@
 
 // Rust Security Wrapper for CryptoQuest Arbitrage Bot
+use rand::rngs::StdRng;
+use rand::Rng;
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
-use tokio::time::{sleep, Duration};
+use std::collections::{BTreeMap, BTreeSet, BinaryHeap, HashMap};
+use std::fmt;
+use std::future::Future;
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+use tokio::time::{sleep, Duration, Instant, MissedTickBehavior};
+use tracing::{field, info_span, Instrument};
+use ethereum_types::U512;
+use ethabi::Token;
+use futures::stream::{self, Stream, StreamExt};
 use web3::types::{Address, U256};
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+use config::{bridge_timeouts_ms, millis};
+use signer::signed_transaction_chain_id;
+
+mod allowance;
+mod backtest;
+mod bridge;
+mod builder;
+mod circuit;
+mod config;
+mod confirmer;
+mod cost;
+mod dedup;
+mod error;
+mod events;
+mod executor;
+mod flash_loan;
+mod gas;
+mod health;
+mod journal;
+mod ledger;
+mod metrics;
+mod nonce;
+mod observer;
+mod permit;
+mod pools;
+mod price;
+mod rate_limit;
+mod route;
+mod signer;
+mod stable_swap;
+mod subscription;
+mod validation;
+mod weighted;
+
+pub use allowance::{APPROVE_GAS_LIMIT, WRAP_GAS_LIMIT};
+pub use backtest::{BacktestReport, BacktestTrade, Backtester};
+pub use bridge::{AggLayerBridge, BridgeProvider, BridgeQuote};
+pub use builder::ArbitrageCoreBuilder;
+pub use circuit::CircuitBreaker;
+pub use config::ArbitrageConfig;
+pub use confirmer::{Confirmer, MockConfirmer, ReceiptStatus, RpcConfirmer};
+pub use cost::{DefaultExecutionCostModel, ExecutionCostModel};
+pub use dedup::ExecutionLedger;
+pub use error::{redact_url, ArbitrageError};
+pub use executor::{send_private_transaction, MockExecutor, SubmissionMode, SwapAction, SwapRequest, TradeExecutor};
+pub use flash_loan::{AaveFlashLoanProvider, FlashLoanProvider};
+pub use gas::{l1_data_gas, GasLimitBand, GasMultipliers, GasOracle, GasSuggestion, Urgency, MAX_GAS_MULTIPLIER};
+pub use health::{HealthStatus, HEALTH_CHECK_TIMEOUT};
+pub use journal::{InMemoryJournal, JournalEntry, JsonLinesJournal, TradeJournal};
+pub use ledger::LedgerSigner;
+pub use metrics::Metrics;
+pub use nonce::NonceManager;
+pub use observer::{ExecutionObserver, ExecutionStage};
+pub use permit::PermitData;
+pub use pools::{PoolCache, PoolFetcher, V2_DEFAULT_FEE_TIER};
+pub use price::{ChainlinkPriceOracle, PriceCache, PriceOracle, StaticPriceOracle};
+pub use rate_limit::RateLimiter;
+pub use route::{ArbitrageRoute, MAX_ROUTE_LENGTH};
+pub use signer::{encode_signed_transaction, encode_unsigned_transaction, LocalSigner, RawTransaction, Signer, UnsignedTransaction};
+pub use validation::{RejectionReason, ValidationOutcome};
+// Re-exported so callers can cancel executions without depending on tokio-util
+pub use tokio_util::sync::CancellationToken;
+
+// Fee tiers are expressed in hundredths of a basis point (3000 = 0.3%)
+const FEE_TIER_DENOMINATOR: f64 = 1_000_000.0;
+
+// Opportunities seen within the same window of this many seconds share an id
+const OPPORTUNITY_ID_BUCKET_SECS: u64 = 60;
+
+// Pool pairs evaluated concurrently during a scan
+const SCAN_CONCURRENCY: usize = 16;
+
+// Bisection steps when sizing a partial fill on curves without a closed-form inverse
+const FILL_SEARCH_ITERATIONS: usize = 64;
+
+// Highest gas price, or EIP-1559 max fee, a transaction may be built or replaced with: 500 gwei
+const MAX_GAS_PRICE_WEI: u64 = 500_000_000_000;
+
+// Loosest slippage an opportunity may ask for through max_slippage_override: 5%
+pub const MAX_SLIPPAGE_OVERRIDE: f64 = 0.05;
+
+// Fixed-point scale of the integer price impact path: PRICE_IMPACT_SCALE is 100%
+pub const PRICE_IMPACT_SCALE: u64 = 1_000_000_000_000_000_000;
+
+// swapExactTokensForTokens calldata for a two-token path: selector, five words, path length, two addresses
+const SWAP_CALLDATA_BYTES: usize = 4 + 32 * 8;
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct ArbitrageOpportunity {
     pub source_pool: PoolInfo,
     pub target_pool: PoolInfo,
@@ -15,9 +113,200 @@ pub struct ArbitrageOpportunity {
     pub net_profit: f64,
     pub confidence: f64,
     pub timestamp: u64,
+    // Which leg sells token0; older payloads without it always sold on the source pool
+    #[serde(default)]
+    pub direction: TradeDirection,
+    // Integer representation of the amounts above; preferred over the f64 fields when present
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub profit_potential_wei: Option<TokenAmount>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub required_amount_wei: Option<TokenAmount>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub execution_cost_wei: Option<TokenAmount>,
+    // When present, validation recomputes net profit with estimated gas instead of trusting net_profit
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub gas_quote: Option<GasQuote>,
+    // Together with gas_quote, lets execution compare profit and total gas cost in USD
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub usd_prices: Option<UsdPrices>,
+    // The route starts from the source network's native coin, wrapped before the source
+    // swap, and/or ends in the target network's native coin, unwrapped after the target swap
+    #[serde(default)]
+    pub native_entry: bool,
+    #[serde(default)]
+    pub native_exit: bool,
+    // Picks the gas multiplier; Normal for payloads without it
+    #[serde(default)]
+    pub urgency: Urgency,
+    // Slippage tolerance for this trade instead of the core's max_slippage, capped at
+    // MAX_SLIPPAGE_OVERRIDE
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_slippage_override: Option<f64>,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TradeDirection {
+    // Sell token0 on the source pool and buy it back on the target pool
+    #[default]
+    SellSourceBuyTarget,
+    // Buy token0 on the source pool and sell it on the target pool
+    BuySourceSellTarget,
+}
+
+impl TradeDirection {
+    pub fn source_action(&self) -> SwapAction {
+        match self {
+            TradeDirection::SellSourceBuyTarget => SwapAction::Sell,
+            TradeDirection::BuySourceSellTarget => SwapAction::Buy,
+        }
+    }
+
+    pub fn target_action(&self) -> SwapAction {
+        match self {
+            TradeDirection::SellSourceBuyTarget => SwapAction::Buy,
+            TradeDirection::BuySourceSellTarget => SwapAction::Sell,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct GasQuote {
+    // Gas prices in wei; native token prices in the opportunity's profit currency
+    pub source_gas_price: U256,
+    pub target_gas_price: U256,
+    pub source_native_price: f64,
+    pub target_native_price: f64,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct UsdPrices {
+    pub source_native: f64,
+    pub target_native: f64,
+    pub profit_token: f64,
+}
+
+// Confidence multiplier for pools holding at least min_liquidity whole tokens. Below 1.0
+// derates thin pools, where slippage and sandwiching cost more; a little above rewards deep ones.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct LiquidityTier {
+    pub min_liquidity: u64,
+    pub multiplier: f64,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TokenAmount {
+    pub amount_wei: U256,
+    pub decimals: u8,
+}
+
+impl TokenAmount {
+    pub fn new(amount_wei: U256, decimals: u8) -> Self {
+        Self { amount_wei, decimals }
+    }
+
+    pub fn rescale(&self, decimals: u8) -> Option<TokenAmount> {
+        // Convert to another decimal precision, truncating when scaling down
+        let amount_wei = if decimals >= self.decimals {
+            let factor = U256::exp10((decimals - self.decimals) as usize);
+            self.amount_wei.checked_mul(factor)?
+        } else {
+            self.amount_wei / U256::exp10((self.decimals - decimals) as usize)
+        };
+
+        Some(TokenAmount { amount_wei, decimals })
+    }
+
+    pub fn to_f64(&self) -> f64 {
+        // Lossy conversion to whole token units, for display only
+        u256_to_f64(self.amount_wei) / 10f64.powi(self.decimals as i32)
+    }
+}
+
+impl ArbitrageOpportunity {
+    pub fn required_amount_raw(&self) -> U256 {
+        // Integer amount to trade, falling back to the f64 field in source pool units
+        match self.required_amount_wei {
+            Some(amount) => amount.amount_wei,
+            None => denormalize_amount(self.required_amount, self.source_pool.decimals),
+        }
+    }
+
+    pub fn profit_wei(&self) -> Option<U256> {
+        self.profit_potential_wei.map(|profit| profit.amount_wei)
+    }
+
+    pub fn opportunity_id(&self) -> String {
+        // Stable across retries of the same trade: pools, direction, size and a coarse time bucket
+        let preimage = format!(
+            "{}:{}:{}:{}:{:?}:{}:{}",
+            self.source_pool.network,
+            self.source_pool.address.to_ascii_lowercase(),
+            self.target_pool.network,
+            self.target_pool.address.to_ascii_lowercase(),
+            self.direction,
+            self.required_amount_raw(),
+            self.timestamp / OPPORTUNITY_ID_BUCKET_SECS
+        );
+
+        format!("0x{}", hex::encode(web3::signing::keccak256(preimage.as_bytes())))
+    }
+
+    pub fn needs_bridge(&self) -> bool {
+        // Pools on one network, e.g. two DEXes on Polygon, trade without a bridge
+        self.source_pool.network != self.target_pool.network
+    }
+
+    pub fn bridged_token(&self) -> &str {
+        // The source leg's output is what crosses the bridge
+        match self.direction {
+            TradeDirection::SellSourceBuyTarget => &self.source_pool.token1,
+            TradeDirection::BuySourceSellTarget => &self.source_pool.token0,
+        }
+    }
+
+    pub fn net_profit_wei(&self) -> Option<U256> {
+        // Profit minus execution cost in the profit's precision; zero when the trade loses money
+        let profit = self.profit_potential_wei?;
+        let cost = self.execution_cost_wei?.rescale(profit.decimals)?;
+
+        Some(profit.amount_wei.saturating_sub(cost.amount_wei))
+    }
+}
+
+impl PartialOrd for ArbitrageOpportunity {
+    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+        // Ordered by net profit alone; None when either is NaN
+        self.net_profit.partial_cmp(&other.net_profit)
+    }
+}
+
+// Priority wrapper for a BinaryHeap: the greatest element is the one scan results list first,
+// so popping yields the most profitable opportunity with ties broken by pool addresses
+#[derive(Debug, Clone)]
+pub struct RankedOpportunity(pub ArbitrageOpportunity);
+
+impl PartialEq for RankedOpportunity {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == std::cmp::Ordering::Equal
+    }
+}
+
+impl Eq for RankedOpportunity {}
+
+impl PartialOrd for RankedOpportunity {
+    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for RankedOpportunity {
+    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+        scan_order(&self.0, &other.0).reverse()
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct PoolInfo {
     pub address: String,
     pub network: String,
@@ -26,6 +315,211 @@ pub struct PoolInfo {
     pub price: f64,
     pub liquidity: u128,
     pub fee_tier: u32,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub reserve0: Option<u128>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub reserve1: Option<u128>,
+    // Decimals of the amounts this pool reports (liquidity and reserves)
+    #[serde(default = "default_decimals")]
+    pub decimals: u8,
+    // Pricing curve; payloads without it are treated as constant product
+    #[serde(default)]
+    pub kind: PoolKind,
+    // DEX the pool belongs to, e.g. "quickswap"; picks the router from the network's routers
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub dex: String,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum PoolKind {
+    // Uniswap V2-style x * y = k
+    #[default]
+    ConstantProduct,
+    // Curve-style stable pool with amplification coefficient amp
+    StableSwap { amp: u64 },
+    // Uniswap V3-style pool; liquidity is the active in-range liquidity
+    ConcentratedV3,
+    // Balancer-style weighted pool; weights are the value shares of token0 and token1
+    Weighted { weight0: f64, weight1: f64 },
+}
+
+fn candidate_pairs(pools: &[PoolInfo]) -> Vec<(PoolInfo, PoolInfo)> {
+    // Pools trading the same tokens on different venues: another network, or another DEX
+    let mut pairs = Vec::new();
+    for (i, a) in pools.iter().enumerate() {
+        for b in &pools[i + 1..] {
+            let venues_differ = a.network != b.network || a.dex != b.dex;
+            if venues_differ && a.token0 == b.token0 && a.token1 == b.token1 {
+                pairs.push((a.clone(), b.clone()));
+            }
+        }
+    }
+    pairs
+}
+
+fn scan_order(a: &ArbitrageOpportunity, b: &ArbitrageOpportunity) -> std::cmp::Ordering {
+    // Net profit descending, ties broken by pool addresses so the order never depends on
+    // which validation finished first
+    b.net_profit.total_cmp(&a.net_profit)
+        .then_with(|| a.source_pool.address.cmp(&b.source_pool.address))
+        .then_with(|| a.target_pool.address.cmp(&b.target_pool.address))
+}
+
+fn depth_factor(kind: PoolKind) -> f64 {
+    // Share of a pool's liquidity that trades like a 50/50 pool of that size
+    match kind {
+        PoolKind::Weighted { weight0, weight1 } => weighted::depth_factor(weight0, weight1),
+        _ => 1.0,
+    }
+}
+
+fn default_decimals() -> u8 {
+    18
+}
+
+pub fn normalize_amount(raw: u128, decimals: u8) -> f64 {
+    // Raw integer amount to whole token units
+    raw as f64 / 10f64.powi(decimals as i32)
+}
+
+pub fn denormalize_amount(value: f64, decimals: u8) -> U256 {
+    // Whole token units back to a raw integer amount, truncating fractional wei
+    if !value.is_finite() || value <= 0.0 {
+        return U256::zero();
+    }
+
+    let raw = value * 10f64.powi(decimals as i32);
+    U256::from_dec_str(&format!("{:.0}", raw.trunc())).unwrap_or_else(|_| U256::max_value())
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct V3SwapEstimate {
+    pub sqrt_price_next_x96: U256,
+    pub amount_out: U256,
+    pub price_impact: f64,
+    // Set when the swap would leave the active tick; the estimate then ignores the crossing
+    pub crosses_tick: bool,
+}
+
+// How calculate_optimal_amount sized a trade
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct OptimalAmount {
+    pub amount: U256,
+    // The shallower pool, whose liquidity the amount is a fraction of
+    pub binding_liquidity: PoolSide,
+    // Price-difference multiplier after clamping to the configured range; 0.0 when no size
+    // could be computed
+    pub price_multiplier: f64,
+    // Set when the max_trade_fraction cap cut the amount
+    pub clamped: bool,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PoolSide {
+    Source,
+    Target,
+}
+
+// What scan_pool_addresses found, and whether some pools had to be left out
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ScanResult {
+    pub opportunities: Vec<ArbitrageOpportunity>,
+    // Set when any requested pool was skipped, so the opportunities may be incomplete
+    pub partial_results: bool,
+    // Networks whose RPC failed during the scan; none of their pools were used
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub unreachable_networks: Vec<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PlannedTrade {
+    pub network: String,
+    pub pool: String,
+    pub amount: U256,
+    pub action: SwapAction,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum ExecutionResult {
+    Executed {
+        source_tx_hash: String,
+        // Empty when both legs ran on one network with no bridge in between
+        bridge_tx_hash: String,
+        target_tx_hash: String,
+        // The source leg, in its input token; below requested when shrinking liquidity forced
+        // a partial fill
+        filled: U256,
+        requested: U256,
+        report: ExecutionReport,
+    },
+    // Nothing was submitted; describes what a live run would have done
+    DryRun {
+        planned_trades: Vec<PlannedTrade>,
+        estimated_gas: U256,
+    },
+    // Hops of a multi-hop route in order; bridges only where the route changes network
+    RouteExecuted {
+        tx_hashes: Vec<String>,
+        bridge_tx_hashes: Vec<String>,
+    },
+    // Borrow, every hop and the repayment settled in a single transaction
+    FlashLoanExecuted {
+        tx_hash: String,
+        loan_amount: U256,
+        loan_fee: U256,
+    },
+}
+
+impl ExecutionResult {
+    pub fn is_dry_run(&self) -> bool {
+        matches!(self, ExecutionResult::DryRun { .. })
+    }
+
+    pub fn target_tx_hash(&self) -> Option<&str> {
+        match self {
+            ExecutionResult::Executed { target_tx_hash, .. } => Some(target_tx_hash),
+            ExecutionResult::DryRun { .. } => None,
+            ExecutionResult::RouteExecuted { tx_hashes, .. } => tx_hashes.last().map(String::as_str),
+            ExecutionResult::FlashLoanExecuted { tx_hash, .. } => Some(tx_hash),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ExecutionReport {
+    // Profits in the opportunity's profit currency; expected is the pre-trade net_profit
+    pub expected_net_profit: f64,
+    // Wallet balance change of the route's token, less the gas actually paid. Without a
+    // signer or readable balances this is the estimate scaled by the fill (from_balances false).
+    pub realized_net_profit: f64,
+    // Wei summed over all legs: source and bridge in the source native token, target in the
+    // target native token
+    pub total_gas_paid: U256,
+    // Shortfall of realized gross profit against the fill-scaled estimate, as a fraction of
+    // the estimate; negative when execution beat it
+    pub slippage_incurred: f64,
+    // Transaction hashes in order: wrap when the entry is native, source, bridge, target,
+    // then unwrap when the exit is native
+    pub leg_hashes: Vec<String>,
+    pub from_balances: bool,
+}
+
+// Outcome of simulate_arbitrage. Amounts are raw: the source input and the target output are
+// in the route's start token, the rest in the bridged token. Profits and cost are in the
+// profit token, gross_profit already net of pool fees and the bridge's cut.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SimulationResult {
+    pub source_amount_in: U256,
+    pub source_amount_out: U256,
+    // None when both pools are on one network
+    pub bridge_quote: Option<BridgeQuote>,
+    pub target_amount_in: U256,
+    pub target_amount_out: U256,
+    pub gross_profit: f64,
+    pub execution_cost: f64,
+    pub net_profit: f64,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -36,251 +530,3578 @@ pub struct SecureTransaction {
     pub gas_price: U256,
     pub data: Vec<u8>,
     pub nonce: U256,
+    // 0 for legacy transactions, 2 for EIP-1559; gas_price then holds max_fee_per_gas
+    #[serde(default)]
+    pub tx_type: u8,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_fee_per_gas: Option<U256>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_priority_fee_per_gas: Option<U256>,
 }
 
-pub struct ArbitrageCore {
-    polygon_rpc: String,
-    base_rpc: String,
-    max_slippage: f64,
-    gas_multiplier: f64,
+#[derive(Clone, PartialEq, Serialize, Deserialize)]
+pub struct NetworkConfig {
+    pub rpc_url: String,
+    pub chain_id: u64,
+    pub base_gas: u64,
+    pub is_l2: bool,
+    // OP-stack chains add an L1 data fee on top of execution gas
+    #[serde(default)]
+    pub op_stack: bool,
+    // WebSocket endpoint for subscriptions; derived from rpc_url when unset
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub ws_url: Option<String>,
+    // WETH-style contract wrapping the native coin (WMATIC on Polygon, WETH on Base)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub wrapped_native: Option<Address>,
+    // Overrides the core's gas limit band, e.g. where L2 gas accounting runs higher
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub gas_limit_band: Option<GasLimitBand>,
+    // Router contract per DEX name, for pools that name their dex
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub routers: BTreeMap<String, Address>,
 }
 
-impl ArbitrageCore {
-    pub fn new(polygon_rpc: String, base_rpc: String) -> Self {
+impl fmt::Debug for NetworkConfig {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        // Endpoints are redacted so a logged config doesn't leak API keys
+        f.debug_struct("NetworkConfig")
+            .field("rpc_url", &redact_url(&self.rpc_url))
+            .field("chain_id", &self.chain_id)
+            .field("base_gas", &self.base_gas)
+            .field("is_l2", &self.is_l2)
+            .field("op_stack", &self.op_stack)
+            .field("ws_url", &self.ws_url.as_deref().map(redact_url))
+            .field("wrapped_native", &self.wrapped_native)
+            .field("gas_limit_band", &self.gas_limit_band)
+            .field("routers", &self.routers)
+            .finish()
+    }
+}
+
+impl NetworkConfig {
+    pub fn polygon(rpc_url: String) -> Self {
         Self {
-            polygon_rpc,
-            base_rpc,
-            max_slippage: 0.02, // 2%
-            gas_multiplier: 1.2,
+            rpc_url,
+            chain_id: 137,
+            base_gas: 150_000,
+            is_l2: false,
+            op_stack: false,
+            ws_url: None,
+            wrapped_native: "0x0d500B1d8E8eF31E21C99d1Db9A6444d3ADf1270".parse().ok(),
+            gas_limit_band: None,
+            routers: BTreeMap::new(),
         }
     }
 
-    pub async fn validate_opportunity(&self, opportunity: &ArbitrageOpportunity) -> Result<bool, Box<dyn std::error::Error>> {
-        // Validate arbitrage opportunity with strict security checks
-        
-        // Check minimum profit threshold
-        if opportunity.net_profit <= 0.0 {
-            return Ok(false);
+    pub fn base(rpc_url: String) -> Self {
+        // Includes the cross-chain overhead previously added for non-Polygon networks
+        Self {
+            rpc_url,
+            chain_id: 8453,
+            base_gas: 450_000,
+            is_l2: true,
+            op_stack: true,
+            ws_url: None,
+            wrapped_native: "0x4200000000000000000000000000000000000006".parse().ok(),
+            gas_limit_band: None,
+            routers: BTreeMap::new(),
         }
+    }
 
-        // Check confidence threshold
-        if opportunity.confidence < 0.7 {
-            return Ok(false);
-        }
+    pub fn with_ws_url(mut self, ws_url: impl Into<String>) -> Self {
+        self.ws_url = Some(ws_url.into());
+        self
+    }
 
-        // Validate pool addresses
-        if !self.is_valid_address(&opportunity.source_pool.address) ||
-           !self.is_valid_address(&opportunity.target_pool.address) {
-            return Ok(false);
-        }
+    pub fn with_wrapped_native(mut self, wrapped_native: Address) -> Self {
+        self.wrapped_native = Some(wrapped_native);
+        self
+    }
 
-        // Check maximum slippage
-        let price_impact = self.calculate_price_impact(
-            opportunity.required_amount,
-            opportunity.source_pool.liquidity as f64
-        );
+    pub fn with_router(mut self, dex: impl Into<String>, router: Address) -> Self {
+        self.routers.insert(dex.into(), router);
+        self
+    }
 
-        if price_impact > self.max_slippage {
-            return Ok(false);
+    pub fn with_gas_limit_band(mut self, min: u64, max: u64) -> Self {
+        self.gas_limit_band = Some(GasLimitBand::new(min, max));
+        self
+    }
+
+    pub fn websocket_url(&self) -> String {
+        match &self.ws_url {
+            Some(ws_url) => ws_url.clone(),
+            None if self.rpc_url.starts_with("https://") => self.rpc_url.replacen("https://", "wss://", 1),
+            None => self.rpc_url.replacen("http://", "ws://", 1),
         }
+    }
+}
+
+pub struct ArbitrageCore {
+    networks: HashMap<String, NetworkConfig>,
+    max_slippage: f64,
+    max_trade_fraction: f64,
+    max_amount_per_trade: U256,
+    max_capital_per_cycle: U256,
+    deployed_capital: Mutex<U256>,
+    liquidity_fraction: f64,
+    price_multiplier_min: f64,
+    price_multiplier_max: f64,
+    gas_multipliers: GasMultipliers,
+    gas_limit_band: GasLimitBand,
+    min_confidence: f64,
+    min_net_profit: f64,
+    min_profit_bps: u32,
+    net_profit_tolerance: f64,
+    max_sandwich_risk: Option<f64>,
+    max_competing_txs: Option<usize>,
+    max_hops: usize,
+    max_bridges: usize,
+    // Sorted by min_liquidity
+    liquidity_tiers: Vec<LiquidityTier>,
+    max_retries: u32,
+    retry_budget: Option<u32>,
+    retry_budget_window: Option<Duration>,
+    retry_base_delay: Duration,
+    max_opportunity_age: Duration,
+    deadline_buffer: Duration,
+    bridge_timeouts: HashMap<(String, String), Duration>,
+    default_bridge_timeout: Duration,
+    min_confirmations: HashMap<String, u64>,
+    confirmation_poll_interval: Duration,
+    speed_up_after: Option<Duration>,
+    rpc_timeout: Duration,
+    dry_run: bool,
+    submission_mode: SubmissionMode,
+    concurrent_legs: bool,
+    bridge: Box<dyn BridgeProvider>,
+    confirmer: Box<dyn Confirmer>,
+    executor: Box<dyn TradeExecutor>,
+    journal: Option<Box<dyn TradeJournal>>,
+    observer: Option<Box<dyn ExecutionObserver>>,
+    flash_loan: Option<Box<dyn FlashLoanProvider>>,
+    pool_fetcher: Option<Box<dyn PoolFetcher>>,
+    signer: Option<Box<dyn Signer>>,
+    approve_max: bool,
+    use_permits: bool,
+    // Kill switch: set by halt() or a signal, or implied while halt_file exists
+    halted: Arc<AtomicBool>,
+    halt_file: Option<PathBuf>,
+    price_oracle: Option<Box<dyn PriceOracle>>,
+    cost_model: Box<dyn ExecutionCostModel>,
+    bridge_fee: f64,
+    price_cache: PriceCache,
+    pool_cache: PoolCache,
+    gas_oracle: GasOracle,
+    rate_limiter: RateLimiter,
+    nonce_manager: NonceManager,
+    // Locally signed transactions still pending, by hash, so a stalled one can be replaced
+    replaceable: Mutex<HashMap<String, (String, SecureTransaction)>>,
+    // Signed permits waiting for their swap, by network, token and spender
+    permits: Mutex<HashMap<(String, Address, Address), permit::SignedPermit>>,
+    metrics: Metrics,
+    circuit_breaker: CircuitBreaker,
+    executions: ExecutionLedger,
+    rng: Arc<Mutex<StdRng>>,
+}
 
-        Ok(true)
+impl ArbitrageCore {
+    pub fn builder() -> ArbitrageCoreBuilder {
+        ArbitrageCoreBuilder::new()
     }
 
-    pub fn calculate_optimal_amount(&self, source_liquidity: u128, target_liquidity: u128, price_diff: f64) -> f64 {
-        // Calculate optimal arbitrage amount using geometric mean
-        let min_liquidity = std::cmp::min(source_liquidity, target_liquidity) as f64;
-        let base_amount = min_liquidity * 0.01; // 1% of minimum liquidity
-        
-        // Adjust based on price difference
-        let price_multiplier = (price_diff * 10.0).min(2.0).max(0.5);
-        
-        base_amount * price_multiplier
+    pub fn from_config(config: ArbitrageConfig) -> Result<Self, ArbitrageError> {
+        // Default backends; use ArbitrageCoreBuilder::config to combine a config with custom ones
+        Self::builder().config(config).build()
     }
 
-    pub fn calculate_price_impact(&self, amount: f64, liquidity: f64) -> f64 {
-        // Calculate price impact using constant product formula
-        if liquidity <= 0.0 {
-            return 1.0; // 100% impact if no liquidity
+    pub fn to_config(&self) -> ArbitrageConfig {
+        // Current settings, including runtime changes such as set_min_confidence
+        ArbitrageConfig {
+            networks: self.networks.clone().into_iter().collect(),
+            max_slippage: self.max_slippage,
+            max_trade_fraction: self.max_trade_fraction,
+            max_amount_per_trade: self.max_amount_per_trade,
+            max_capital_per_cycle: self.max_capital_per_cycle,
+            max_sandwich_risk: self.max_sandwich_risk,
+            max_competing_txs: self.max_competing_txs,
+            max_hops: self.max_hops,
+            max_bridges: self.max_bridges,
+            liquidity_tiers: self.liquidity_tiers.clone(),
+            liquidity_fraction: self.liquidity_fraction,
+            price_multiplier_min: self.price_multiplier_min,
+            price_multiplier_max: self.price_multiplier_max,
+            gas_multipliers: self.gas_multipliers.clone(),
+            max_gas_limit: self.gas_limit_band.max,
+            min_confidence: self.min_confidence,
+            min_net_profit: self.min_net_profit,
+            min_profit_bps: self.min_profit_bps,
+            max_retries: self.max_retries,
+            retry_budget: self.retry_budget,
+            retry_budget_window_ms: self.retry_budget_window.map(millis),
+            retry_base_delay_ms: millis(self.retry_base_delay),
+            max_opportunity_age_ms: millis(self.max_opportunity_age),
+            deadline_buffer_ms: millis(self.deadline_buffer),
+            bridge_timeouts_ms: bridge_timeouts_ms(&self.bridge_timeouts),
+            default_bridge_timeout_ms: millis(self.default_bridge_timeout),
+            min_confirmations: self.min_confirmations.clone().into_iter().collect(),
+            rate_limits: self.rate_limiter.limits().into_iter().collect(),
+            confirmation_poll_interval_ms: millis(self.confirmation_poll_interval),
+            speed_up_after_ms: self.speed_up_after.map(millis),
+            rpc_timeout_ms: millis(self.rpc_timeout),
+            dry_run: self.dry_run,
+            submission_mode: self.submission_mode.clone(),
+            concurrent_legs: self.concurrent_legs,
+            failure_threshold: self.circuit_breaker.failure_threshold(),
+            failure_window_ms: millis(self.circuit_breaker.failure_window()),
+            cooldown_ms: millis(self.circuit_breaker.cooldown()),
+            dedup_window_ms: millis(self.executions.retention()),
+            approve_max: self.approve_max,
+            use_permits: self.use_permits,
+            halt_file: self.halt_file.clone(),
+            pool_cache_ttl_ms: millis(self.pool_cache.ttl()),
+            gas_price_ttl_ms: millis(self.gas_oracle.ttl()),
+            price_cache_ttl_ms: millis(self.price_cache.ttl()),
+            bridge_fee: self.bridge_fee,
         }
-        
-        amount / (liquidity + amount)
     }
 
-    pub async fn estimate_gas_cost(&self, network: &str, transaction: &SecureTransaction) -> Result<U256, Box<dyn std::error::Error>> {
-        // Estimate gas cost with safety multiplier
-        let base_gas = U256::from(150_000);
-        let cross_chain_gas = U256::from(300_000);
-        
-        let estimated_gas = if network == "polygon" {
-            base_gas
-        } else {
-            base_gas + cross_chain_gas
-        };
-        
-        // Apply safety multiplier
-        let safe_gas = estimated_gas * U256::from((self.gas_multiplier * 100.0) as u64) / U256::from(100);
-        
-        Ok(safe_gas)
+    pub fn new(polygon_rpc: String, base_rpc: String) -> Self {
+        Self::builder()
+            .polygon_rpc(polygon_rpc)
+            .base_rpc(base_rpc)
+            .build()
+            .expect("default configuration is valid")
     }
 
-    pub fn secure_transaction_builder(&self, 
-        to: &str, 
-        value: u64, 
-        data: Vec<u8>,
-        gas_limit: u64,
-        gas_price: u64,
-        nonce: u64
-    ) -> Result<SecureTransaction, Box<dyn std::error::Error>> {
-        // Build transaction with security validations
-        
-        let to_address = to.parse::<Address>()
-            .map_err(|_| "Invalid destination address")?;
-        
-        // Validate gas parameters
-        if gas_limit < 21_000 {
-            return Err("Gas limit too low".into());
-        }
-        
-        if gas_price > 500_000_000_000u64 { // 500 Gwei max
-            return Err("Gas price too high".into());
-        }
+    pub fn new_with_seed(polygon_rpc: String, base_rpc: String, seed: u64) -> Self {
+        // Reproducible mock hashes and confirmation timing for tests
+        Self::builder()
+            .polygon_rpc(polygon_rpc)
+            .base_rpc(base_rpc)
+            .seed(seed)
+            .build()
+            .expect("default configuration is valid")
+    }
 
-        Ok(SecureTransaction {
-            to: to_address,
-            value: U256::from(value),
-            gas_limit: U256::from(gas_limit),
-            gas_price: U256::from(gas_price),
-            data,
-            nonce: U256::from(nonce),
-        })
+    pub fn with_executor(polygon_rpc: String, base_rpc: String, executor: Box<dyn TradeExecutor>) -> Self {
+        Self::builder()
+            .polygon_rpc(polygon_rpc)
+            .base_rpc(base_rpc)
+            .executor(executor)
+            .build()
+            .expect("default configuration is valid")
     }
 
-    pub async fn execute_cross_chain_arbitrage(&self, opportunity: ArbitrageOpportunity) -> Result<String, Box<dyn std::error::Error>> {
-        // Execute cross-chain arbitrage with enhanced security
-        
-        // Validate opportunity first
-        if !self.validate_opportunity(&opportunity).await? {
-            return Err("Opportunity validation failed".into());
-        }
+    pub fn add_network(&mut self, name: String, config: NetworkConfig) {
+        self.nonce_manager.set_endpoint(&name, &config.rpc_url);
+        self.networks.insert(name, config);
+    }
 
-        // Step 1: Execute source trade
-        let source_tx_hash = self.execute_trade(
-            &opportunity.source_pool.network,
-            &opportunity.source_pool.address,
-            opportunity.required_amount,
-            "sell"
-        ).await?;
+    pub fn nonce_manager(&self) -> &NonceManager {
+        &self.nonce_manager
+    }
 
-        // Step 2: Bridge tokens
-        let bridge_tx_hash = self.bridge_tokens(
-            &opportunity.source_pool.network,
-            &opportunity.target_pool.network,
-            opportunity.required_amount
-        ).await?;
+    pub fn set_rate_limit(&self, network: &str, requests_per_second: u32) {
+        // Every RPC this core issues for the network waits on the limit; 0 removes it
+        self.rate_limiter.set_rate_limit(network, requests_per_second);
+    }
 
-        // Step 3: Wait for bridge confirmation
-        self.wait_for_confirmation(&bridge_tx_hash, 600).await?;
+    pub fn metrics(&self) -> &Metrics {
+        &self.metrics
+    }
 
-        // Step 4: Execute target trade
-        let target_tx_hash = self.execute_trade(
-            &opportunity.target_pool.network,
-            &opportunity.target_pool.address,
-            opportunity.required_amount,
-            "buy"
-        ).await?;
+    pub fn journal(&self) -> Option<&dyn TradeJournal> {
+        self.journal.as_deref()
+    }
 
-        Ok(target_tx_hash)
+    pub fn submission_mode(&self) -> &SubmissionMode {
+        &self.submission_mode
     }
 
-    async fn execute_trade(&self, network: &str, pool: &str, amount: f64, action: &str) -> Result<String, Box<dyn std::error::Error>> {
-        // Mock implementation - replace with actual Web3 calls
-        println!("Executing {} trade on {} for {} tokens in pool {}", action, network, amount, pool);
-        
-        // Simulate transaction hash
-        Ok(format!("0x{:064x}", rand::random::<u64>()))
+    pub fn pool_cache(&self) -> &PoolCache {
+        &self.pool_cache
     }
 
-    async fn bridge_tokens(&self, source: &str, target: &str, amount: f64) -> Result<String, Box<dyn std::error::Error>> {
-        // Mock implementation - replace with AggLayer bridge calls
-        println!("Bridging {} tokens from {} to {}", amount, source, target);
-        
-        // Simulate bridge transaction
-        Ok(format!("0x{:064x}", rand::random::<u64>()))
+    pub fn reset_circuit(&self) {
+        self.circuit_breaker.reset();
     }
 
-    async fn wait_for_confirmation(&self, tx_hash: &str, timeout_seconds: u64) -> Result<(), Box<dyn std::error::Error>> {
-        // Wait for transaction confirmation with timeout
-        let start = std::time::Instant::now();
-        let timeout = Duration::from_secs(timeout_seconds);
-        
-        while start.elapsed() < timeout {
-            // Mock confirmation check - replace with actual RPC calls
-            if rand::random::<f64>() > 0.9 { // 10% chance per check
-                println!("Transaction {} confirmed", tx_hash);
-                return Ok(());
+    pub fn halt(&self) {
+        // Stops new executions and scans; executions already running finish, so no funds are
+        // left mid-bridge
+        self.halted.store(true, Ordering::SeqCst);
+        tracing::warn!("kill switch engaged, execution halted");
+    }
+
+    pub fn resume(&self) {
+        // Clears halt() and signals; a halt_file that still exists keeps the core halted
+        self.halted.store(false, Ordering::SeqCst);
+    }
+
+    pub fn is_halted(&self) -> bool {
+        self.halted.load(Ordering::SeqCst) || self.halt_file.as_ref().is_some_and(|path| path.exists())
+    }
+
+    #[cfg(unix)]
+    pub fn halt_on_signal(&self) -> Result<tokio::task::JoinHandle<()>, ArbitrageError> {
+        // Halt on every SIGUSR1, so operators can stop the bot with kill -USR1 and keep the
+        // process; resume() lifts it. Abort the handle to stop listening.
+        let mut signals = tokio::signal::unix::signal(tokio::signal::unix::SignalKind::user_defined1())
+            .map_err(|err| ArbitrageError::InvalidConfig(format!("cannot listen for SIGUSR1: {}", err)))?;
+        let halted = self.halted.clone();
+
+        Ok(tokio::spawn(async move {
+            while signals.recv().await.is_some() {
+                halted.store(true, Ordering::SeqCst);
+                tracing::warn!("SIGUSR1 received, execution halted");
             }
-            
-            sleep(Duration::from_secs(10)).await;
+        }))
+    }
+
+    fn check_halted(&self) -> Result<(), ArbitrageError> {
+        if self.is_halted() {
+            return Err(ArbitrageError::Halted);
         }
-        
-        Err("Transaction confirmation timeout".into())
+        Ok(())
     }
 
-    fn is_valid_address(&self, address: &str) -> bool {
-        // Validate Ethereum address format
-        if !address.starts_with("0x") || address.len() != 42 {
-            return false;
+    pub fn network(&self, name: &str) -> Result<&NetworkConfig, ArbitrageError> {
+        self.networks.get(name).ok_or_else(|| ArbitrageError::UnknownNetwork(name.to_string()))
+    }
+
+    pub fn router_for(&self, pool: &PoolInfo) -> Result<Address, ArbitrageError> {
+        // The network's router for the pool's DEX; pools without a dex are called directly
+        if pool.dex.is_empty() {
+            return pool.address.parse().map_err(|_| ArbitrageError::InvalidAddress(pool.address.clone()));
         }
-        
-        // Check if all characters after 0x are hex
-        address[2..].chars().all(|c| c.is_ascii_hexdigit())
+        self.network(&pool.network)?.routers.get(&pool.dex).copied()
+            .ok_or_else(|| ArbitrageError::InvalidConfig(format!("no {} router configured on {}", pool.dex, pool.network)))
     }
-}
 
-// FFI interface for Python integration
-#[no_mangle]
-pub extern "C" fn create_arbitrage_core(polygon_rpc: *const i8, base_rpc: *const i8) -> *mut ArbitrageCore {
-    let polygon_rpc = unsafe { std::ffi::CStr::from_ptr(polygon_rpc).to_str().unwrap().to_string() };
-    let base_rpc = unsafe { std::ffi::CStr::from_ptr(base_rpc).to_str().unwrap().to_string() };
-    
-    let core = ArbitrageCore::new(polygon_rpc, base_rpc);
-    Box::into_raw(Box::new(core))
-}
+    pub async fn verify_network(&self, network: &str, expected_chain_id: u64) -> Result<(), ArbitrageError> {
+        let config = self.network(network)?;
+        self.rate_limiter.acquire(network).await;
+        let transport = web3::transports::Http::new(&config.rpc_url)?;
+        let actual = self.with_rpc_timeout(network, web3::Web3::new(transport).eth().chain_id()).await??;
 
-#[no_mangle]
-pub extern "C" fn validate_opportunity_ffi(
-    core: *mut ArbitrageCore, 
-    opportunity_json: *const i8
-) -> bool {
-    let core = unsafe { &*core };
-    let json_str = unsafe { std::ffi::CStr::from_ptr(opportunity_json).to_str().unwrap() };
-    
-    match serde_json::from_str::<ArbitrageOpportunity>(json_str) {
-        Ok(opportunity) => {
-            match tokio::runtime::Runtime::new().unwrap().block_on(core.validate_opportunity(&opportunity)) {
-                Ok(result) => result,
-                Err(_) => false,
+        if actual != expected_chain_id.into() {
+            return Err(ArbitrageError::ChainIdMismatch {
+                network: network.to_string(),
+                expected: expected_chain_id,
+                actual: actual.low_u64(),
+            });
+        }
+
+        Ok(())
+    }
+
+    pub async fn connect(&self) -> Result<(), ArbitrageError> {
+        // Call once before executing: a mistyped RPC URL must not send trades to the wrong chain
+        for (name, config) in &self.networks {
+            self.verify_network(name, config.chain_id).await?;
+        }
+
+        Ok(())
+    }
+
+    pub async fn health_check(&self) -> HashMap<String, HealthStatus> {
+        // Probe every configured RPC concurrently so one dead endpoint doesn't stall the rest
+        let checks = self.networks.iter().map(|(name, config)| async move {
+            self.rate_limiter.acquire(name).await;
+            let status = health::check_rpc(&config.rpc_url, config.chain_id, HEALTH_CHECK_TIMEOUT).await;
+            if !status.is_ok() {
+                tracing::warn!(network = %name, ?status, "rpc health check failed");
             }
+            (name.clone(), status)
+        });
+
+        futures::future::join_all(checks).await.into_iter().collect()
+    }
+
+    pub fn set_min_confidence(&mut self, min_confidence: f64) -> Result<(), ArbitrageError> {
+        if !(0.0..=1.0).contains(&min_confidence) {
+            return Err(ArbitrageError::InvalidConfig(format!(
+                "min_confidence must be within 0.0..=1.0, got {}",
+                min_confidence
+            )));
         }
-        Err(_) => false,
+
+        self.min_confidence = min_confidence;
+        Ok(())
     }
-}
 
-#[no_mangle]
-pub extern "C" fn calculate_optimal_amount_ffi(
-    core: *mut ArbitrageCore,
-    source_liquidity: u64,
-    target_liquidity: u64,
-    price_diff: f64
-) -> f64 {
-    let core = unsafe { &*core };
-    core.calculate_optimal_amount(source_liquidity as u128, target_liquidity as u128, price_diff)
-}
+    pub fn set_min_confirmations(&mut self, network: &str, min_confirmations: u64) {
+        self.min_confirmations.insert(network.to_string(), min_confirmations);
+    }
 
-#[no_mangle]
-pub extern "C" fn free_arbitrage_core(core: *mut ArbitrageCore) {
-    if !core.is_null() {
-        unsafe { Box::from_raw(core) };
+    pub fn min_confirmations_for(&self, network: &str) -> u64 {
+        // Fast-finality L2s need a single block on top; reorg-prone L1-style chains need more
+        if let Some(min_confirmations) = self.min_confirmations.get(network) {
+            return *min_confirmations;
+        }
+
+        match self.networks.get(network) {
+            Some(config) if config.is_l2 => 1,
+            _ => 12,
+        }
+    }
+
+    pub fn set_min_net_profit(&mut self, min_net_profit: f64) {
+        self.min_net_profit = min_net_profit;
+    }
+
+    pub fn max_slippage_for(&self, opportunity: &ArbitrageOpportunity) -> f64 {
+        // The opportunity's own tolerance when it sets one, within 0..=MAX_SLIPPAGE_OVERRIDE
+        match opportunity.max_slippage_override {
+            Some(slippage) if !slippage.is_nan() => slippage.clamp(0.0, MAX_SLIPPAGE_OVERRIDE),
+            _ => self.max_slippage,
+        }
+    }
+
+    pub fn set_min_profit_bps(&mut self, min_profit_bps: u32) {
+        self.min_profit_bps = min_profit_bps;
+    }
+
+    pub fn set_net_profit_tolerance(&mut self, tolerance: f64) {
+        // Allowed gap between supplied and recomputed net profit, as a fraction of profit potential
+        self.net_profit_tolerance = tolerance.abs();
+    }
+
+    pub fn set_bridge_provider(&mut self, bridge: Box<dyn BridgeProvider>) {
+        self.bridge = bridge;
+    }
+
+    pub async fn validate_opportunity(&self, opportunity: &ArbitrageOpportunity) -> Result<bool, ArbitrageError> {
+        // Stale inputs, mismatched pairs and inconsistent net profit are errors rather than
+        // rejections, as they point at the caller's data rather than the market
+        match self.validate_opportunity_detailed(opportunity).await? {
+            ValidationOutcome::Accepted => Ok(true),
+            ValidationOutcome::Rejected(RejectionReason::Stale { age_secs, max_age_secs }) => {
+                Err(ArbitrageError::StaleOpportunity { age_secs, max_age_secs })
+            }
+            ValidationOutcome::Rejected(reason @ (RejectionReason::TokenPairMismatch { .. } | RejectionReason::NetProfitMismatch { .. })) => {
+                Err(ArbitrageError::ValidationFailed(reason.to_string()))
+            }
+            ValidationOutcome::Rejected(_) => Ok(false),
+        }
+    }
+
+    pub async fn validate_opportunity_detailed(&self, opportunity: &ArbitrageOpportunity) -> Result<ValidationOutcome, ArbitrageError> {
+        // Validate arbitrage opportunity with strict security checks, reporting the first
+        // check that fails. Errors only come from the gas estimate, the bridge quote and the
+        // mempool check.
+        let outcome = self.check_opportunity(opportunity).await?;
+        match &outcome {
+            ValidationOutcome::Accepted => self.metrics.record_validated(),
+            ValidationOutcome::Rejected(reason) => self.metrics.record_rejection(reason.label()),
+        }
+
+        Ok(outcome)
+    }
+
+    async fn check_opportunity(&self, opportunity: &ArbitrageOpportunity) -> Result<ValidationOutcome, ArbitrageError> {
+        let reject = |reason| Ok(ValidationOutcome::Rejected(reason));
+
+        // Prices from an old snapshot say nothing about the market now
+        let age_secs = unix_now().saturating_sub(opportunity.timestamp);
+        if age_secs > self.max_opportunity_age.as_secs() {
+            return reject(RejectionReason::Stale { age_secs, max_age_secs: self.max_opportunity_age.as_secs() });
+        }
+        
+        // Check minimum profit threshold, using the integer amounts when available
+        let (net_profit, is_profitable) = match (opportunity.net_profit_wei(), opportunity.profit_potential_wei) {
+            (Some(net_profit_wei), Some(profit)) => {
+                let net_profit = TokenAmount::new(net_profit_wei, profit.decimals).to_f64();
+                (net_profit, !net_profit_wei.is_zero() && net_profit > self.min_net_profit)
+            }
+            _ => (opportunity.net_profit, opportunity.net_profit > self.min_net_profit),
+        };
+
+        if !is_profitable {
+            return reject(RejectionReason::LowProfit { net_profit, min_net_profit: self.min_net_profit });
+        }
+
+        // The relative floor has to clear as well, so small trades can't pass on a thin margin
+        if self.min_profit_bps > 0 {
+            let required = opportunity.required_amount_wei.map(|amount| amount.to_f64()).unwrap_or(opportunity.required_amount);
+            let profit_bps = if required > 0.0 { net_profit / required * 10_000.0 } else { 0.0 };
+            if profit_bps < self.min_profit_bps as f64 {
+                return reject(RejectionReason::LowProfitBps { profit_bps, min_profit_bps: self.min_profit_bps });
+            }
+        }
+
+        // Check the per-trade capital cap
+        let amount = opportunity.required_amount_raw();
+        if amount > self.max_amount_per_trade {
+            return reject(RejectionReason::TradeSize { amount, max_amount: self.max_amount_per_trade });
+        }
+
+        // Check confidence threshold
+        if opportunity.confidence < self.min_confidence {
+            return reject(RejectionReason::LowConfidence { confidence: opportunity.confidence, min_confidence: self.min_confidence });
+        }
+
+        // Validate pool addresses
+        for address in [&opportunity.source_pool.address, &opportunity.target_pool.address] {
+            if !self.is_valid_address(address) {
+                return reject(RejectionReason::InvalidAddress { address: address.clone() });
+            }
+        }
+
+        // Both legs must trade the same pair, in either token order
+        let source_pool = &opportunity.source_pool;
+        let target_pool = &opportunity.target_pool;
+        let same_token = |a: &str, b: &str| a.eq_ignore_ascii_case(b);
+        let pairs_match = (same_token(&source_pool.token0, &target_pool.token0) && same_token(&source_pool.token1, &target_pool.token1))
+            || (same_token(&source_pool.token0, &target_pool.token1) && same_token(&source_pool.token1, &target_pool.token0));
+
+        if !pairs_match {
+            return reject(RejectionReason::TokenPairMismatch {
+                source_pair: format!("{}/{} ({})", source_pool.token0, source_pool.token1, source_pool.address),
+                target_pair: format!("{}/{} ({})", target_pool.token0, target_pool.token1, target_pool.address),
+            });
+        }
+
+        // Recompute net profit with our own gas estimate and reject inconsistent inputs
+        if let Some(quote) = &opportunity.gas_quote {
+            let recomputed = self.recompute_net_profit(opportunity, quote).await?;
+            let tolerance = opportunity.profit_potential.abs() * self.net_profit_tolerance;
+
+            if (recomputed - opportunity.net_profit).abs() > tolerance {
+                return reject(RejectionReason::NetProfitMismatch { supplied: opportunity.net_profit, recomputed });
+            }
+        }
+
+        // The bridge keeps a fee and may deliver less than was sent, and the target leg can
+        // only sell what arrives
+        if opportunity.needs_bridge() {
+            let bridged_net_profit = net_profit - self.bridge_cost(opportunity).await?;
+            if bridged_net_profit <= self.min_net_profit {
+                return reject(RejectionReason::LowProfit { net_profit: bridged_net_profit, min_net_profit: self.min_net_profit });
+            }
+        }
+
+        // Check maximum slippage, with the source pool's own pricing curve. Decided in fixed
+        // point, since f64 liquidity can round a borderline trade either way
+        let impact = self.pool_price_impact_fixed(source_pool, opportunity.required_amount_raw(), opportunity.direction.source_action());
+        let price_impact = u256_to_f64(impact) / PRICE_IMPACT_SCALE as f64;
+        let max_slippage = self.max_slippage_for(opportunity);
+
+        if impact > denormalize_amount(max_slippage, 18) {
+            return reject(RejectionReason::Slippage { price_impact, max_slippage });
+        }
+
+        // Public mempool trades move the price enough to be worth sandwiching
+        if let Some(max_risk) = self.max_sandwich_risk {
+            let risk = sandwich_risk_from_impact(price_impact, source_pool.fee_tier);
+            if self.submission_mode == SubmissionMode::Public && risk > max_risk {
+                return reject(RejectionReason::SandwichRisk { risk, max_risk });
+            }
+        }
+
+        // Other bots already racing for the same pools usually win, or leave nothing behind
+        if let Some(max_pending) = self.max_competing_txs {
+            for pool in [source_pool, target_pool] {
+                let address = pool.address.parse::<Address>()
+                    .map_err(|_| ArbitrageError::InvalidAddress(pool.address.clone()))?;
+                let pending = self.competing_pending_count(&pool.network, address).await?;
+                if pending > max_pending {
+                    return reject(RejectionReason::Competition { pool: pool.address.clone(), pending, max_pending });
+                }
+            }
+        }
+
+        Ok(ValidationOutcome::Accepted)
+    }
+
+    pub async fn competing_pending_count(&self, network: &str, pool: Address) -> Result<usize, ArbitrageError> {
+        // Transactions in the pending block that call the pool directly or name it in their
+        // calldata, as aggregator and router swaps through a given pair do
+        let config = self.network(network)?;
+        self.rate_limiter.acquire(network).await;
+        let transport = web3::transports::Http::new(&config.rpc_url)?;
+        let pending = self.with_rpc_timeout(
+            network,
+            web3::Web3::new(transport).eth().block_with_txs(web3::types::BlockId::Number(web3::types::BlockNumber::Pending))
+        ).await??;
+
+        let mut word = [0u8; 32];
+        word[12..].copy_from_slice(pool.as_bytes());
+        let competing = pending.map(|block| block.transactions).unwrap_or_default().iter()
+            .filter(|tx| tx.to == Some(pool) || tx.input.0.windows(32).any(|window| window == word))
+            .count();
+
+        Ok(competing)
+    }
+
+    pub async fn scan_opportunities(&self, pools: Vec<PoolInfo>) -> Vec<ArbitrageOpportunity> {
+        // Pair up pools trading the same tokens on different networks, or on different DEXes
+        // of one network, and keep candidates that validate. Each scan starts a new capital cycle.
+        // Results come back in a fixed order: net profit descending, then source pool address,
+        // then target pool address, so the same pools always scan to the same list. Nothing is
+        // found while the kill switch is engaged.
+        if self.is_halted() {
+            tracing::warn!("execution halted, skipping scan");
+            return Vec::new();
+        }
+        *self.deployed_capital.lock().unwrap() = U256::zero();
+
+        let mut found = stream::iter(candidate_pairs(&pools))
+            .map(|(a, b)| async move {
+                let opportunity = self.build_opportunity(&a, &b)?;
+                match self.validate_opportunity(&opportunity).await {
+                    Ok(true) => Some(opportunity),
+                    _ => None,
+                }
+            })
+            .buffer_unordered(SCAN_CONCURRENCY)
+            .filter_map(|opportunity| async move { opportunity })
+            .collect::<Vec<_>>()
+            .await;
+
+        // Most profitable first, keeping only what the cycle's capital can fund
+        found.sort_by(scan_order);
+        let mut budget = self.max_capital_per_cycle;
+        found.retain(|opportunity| {
+            let amount = opportunity.required_amount_raw();
+            let funded = amount <= budget;
+            if funded {
+                budget -= amount;
+            }
+            funded
+        });
+
+        found
+    }
+
+    pub fn opportunity_stream(&self, pools: Vec<PoolInfo>, interval: Duration) -> impl Stream<Item = ArbitrageOpportunity> + '_ {
+        // Rescan every interval and yield each round's validated opportunities, most profitable
+        // first. An opportunity id already yielded within the last id bucket is skipped.
+        let debounce = Duration::from_secs(OPPORTUNITY_ID_BUCKET_SECS);
+        let mut ticker = tokio::time::interval(interval);
+        ticker.set_missed_tick_behavior(MissedTickBehavior::Delay);
+        let state = (ticker, BinaryHeap::new(), HashMap::<String, Instant>::new());
+
+        stream::unfold(state, move |(mut ticker, mut pending, mut seen)| {
+            let pools = pools.clone();
+            async move {
+                while pending.is_empty() {
+                    ticker.tick().await;
+                    seen.retain(|_, yielded_at| yielded_at.elapsed() < debounce);
+
+                    let found = self.scan_opportunities(pools.clone()).await
+                        .into_iter()
+                        .filter(|opportunity| !seen.contains_key(&opportunity.opportunity_id()))
+                        .map(RankedOpportunity);
+                    pending.extend(found);
+                }
+
+                let RankedOpportunity(opportunity) = pending.pop()?;
+                seen.insert(opportunity.opportunity_id(), Instant::now());
+                Some((opportunity, (ticker, pending, seen)))
+            }
+        })
+    }
+
+    pub async fn scan_pool_addresses(&self, pools: &[(String, String)]) -> Result<ScanResult, ArbitrageError> {
+        // Fetch (network, address) pairs through the cache, then scan whatever could be loaded.
+        // An RPC failure marks the pool's network unreachable and drops all of its pools, since
+        // nothing there could execute either; other networks are still scanned. Fails only
+        // when every network asked for is unreachable, or with Halted under the kill switch.
+        self.check_halted()?;
+        let fetched: Vec<(&str, Result<PoolInfo, ArbitrageError>)> = stream::iter(pools)
+            .map(|(network, address)| async move { (network.as_str(), self.pool_state(network, address).await) })
+            .buffer_unordered(SCAN_CONCURRENCY)
+            .collect()
+            .await;
+
+        let mut unreachable_networks: Vec<String> = Vec::new();
+        let mut skipped = false;
+        for (network, result) in &fetched {
+            if let Err(err) = result {
+                skipped = true;
+                if !err.is_transient() {
+                    tracing::warn!(%network, error = %err, "skipping pool that could not be fetched");
+                } else if !unreachable_networks.iter().any(|down| down == network) {
+                    tracing::warn!(%network, error = %err, "network unreachable, scanning without its pools");
+                    unreachable_networks.push(network.to_string());
+                }
+            }
+        }
+        unreachable_networks.sort();
+
+        let requested = pools.iter().map(|(network, _)| network).collect::<BTreeSet<_>>();
+        if !requested.is_empty() && requested.len() == unreachable_networks.len() {
+            return Err(ArbitrageError::Rpc(format!("every network is unreachable: {}", unreachable_networks.join(", "))));
+        }
+
+        let healthy = fetched.into_iter()
+            .filter(|(network, _)| !unreachable_networks.iter().any(|down| down == network))
+            .filter_map(|(_, result)| result.ok())
+            .collect();
+
+        Ok(ScanResult {
+            opportunities: self.scan_opportunities(healthy).await,
+            partial_results: skipped,
+            unreachable_networks,
+        })
+    }
+
+    pub fn subscribe_pool_updates(&self, pools: Vec<PoolInfo>) -> impl Stream<Item = PoolInfo> + '_ {
+        // Live pool state from Sync/Swap events, reconnecting with backoff when a socket drops.
+        // Every update also refreshes the pool cache.
+        let mut by_network: HashMap<String, Vec<PoolInfo>> = HashMap::new();
+        for pool in pools {
+            by_network.entry(pool.network.clone()).or_default().push(pool);
+        }
+
+        let networks = by_network.into_iter()
+            .filter_map(|(network, pools)| match self.networks.get(&network) {
+                Some(config) => Some((network, config.websocket_url(), pools)),
+                None => {
+                    tracing::warn!(%network, "not subscribing to pools on an unknown network");
+                    None
+                }
+            })
+            .collect();
+
+        subscription::pool_updates(networks, self.retry_base_delay, &self.pool_cache)
+    }
+
+    pub fn decode_swap_log(&self, log: &web3::types::Log, fee_tier: u32) -> Result<PoolInfo, ArbitrageError> {
+        // Decode a V2 Sync/Swap or V3 Swap log, dispatching on topic0
+        events::decode_swap_log(log, fee_tier)
+    }
+
+    pub async fn pool_state(&self, network: &str, address: &str) -> Result<PoolInfo, ArbitrageError> {
+        // Serve from the cache while fresh, otherwise fetch and store the result
+        let key = PoolCache::key(network, address);
+        if let Some(pool) = self.pool_cache.get(&key) {
+            return Ok(pool);
+        }
+
+        let fetcher = self.pool_fetcher.as_ref()
+            .ok_or_else(|| ArbitrageError::InvalidConfig("no pool fetcher configured".to_string()))?;
+        self.rate_limiter.acquire(network).await;
+        let pool = self.with_rpc_timeout(network, fetcher.fetch_pool(network, address)).await??;
+        self.pool_cache.insert(key, pool.clone());
+
+        Ok(pool)
+    }
+
+    pub async fn fetch_pool_info(&self, network: &str, pool: Address, kind: PoolKind) -> Result<PoolInfo, ArbitrageError> {
+        // PoolInfo read from the pool contract itself, so scanning needs nothing but addresses
+        let config = self.network(network)?;
+        let info = self.with_rpc_timeout(network, pools::read_pool_info(&config.rpc_url, network, pool, kind, &self.rate_limiter)).await??;
+        self.pool_cache.insert(PoolCache::key(network, &info.address), info.clone());
+
+        Ok(info)
+    }
+
+    pub async fn price_usd(&self, network: &str, token: Address) -> Result<f64, ArbitrageError> {
+        // Cached oracle price of one whole token
+        if let Some(price) = self.price_cache.get(network, token) {
+            return Ok(price);
+        }
+
+        let oracle = self.price_oracle.as_ref()
+            .ok_or_else(|| ArbitrageError::InvalidConfig("no price oracle configured".to_string()))?;
+        self.rate_limiter.acquire(network).await;
+        let price = self.with_rpc_timeout(network, oracle.price_usd(network, token)).await??;
+        self.price_cache.insert(network, token, price);
+
+        Ok(price)
+    }
+
+    async fn oracle_usd_prices(&self, opportunity: &ArbitrageOpportunity) -> Option<UsdPrices> {
+        // Both natives through their wrapped tokens, and the route's start token as the profit token
+        self.price_oracle.as_ref()?;
+        let source = &opportunity.source_pool.network;
+        let target = &opportunity.target_pool.network;
+        let source_native = self.network(source).ok()?.wrapped_native?;
+        let target_native = self.network(target).ok()?.wrapped_native?;
+        let [profit_token, _] = swap_path(&opportunity.source_pool, opportunity.direction.source_action()).ok()?;
+
+        let prices = futures::future::try_join3(
+            self.price_usd(source, source_native),
+            self.price_usd(target, target_native),
+            self.price_usd(source, profit_token),
+        ).await;
+        match prices {
+            Ok((source_native, target_native, profit_token)) => Some(UsdPrices { source_native, target_native, profit_token }),
+            Err(err) => {
+                tracing::warn!(error = %err, "cannot price opportunity in USD, skipping the gas check");
+                None
+            }
+        }
+    }
+
+    pub fn build_opportunity(&self, a: &PoolInfo, b: &PoolInfo) -> Option<ArbitrageOpportunity> {
+        // Evaluate both directions between the pools and keep the more profitable one
+        [TradeDirection::SellSourceBuyTarget, TradeDirection::BuySourceSellTarget]
+            .into_iter()
+            .filter_map(|direction| self.build_directed_opportunity(a, b, direction))
+            .max_by(|x, y| x.net_profit.total_cmp(&y.net_profit))
+    }
+
+    fn build_directed_opportunity(&self,
+        source_pool: &PoolInfo,
+        target_pool: &PoolInfo,
+        direction: TradeDirection
+    ) -> Option<ArbitrageOpportunity> {
+        // token0 is sold where it is priced higher and bought where it is cheaper
+        let (sell_price, buy_price) = match direction {
+            TradeDirection::SellSourceBuyTarget => (source_pool.price, target_pool.price),
+            TradeDirection::BuySourceSellTarget => (target_pool.price, source_pool.price),
+        };
+        if buy_price <= 0.0 || sell_price <= buy_price {
+            return None;
+        }
+
+        let price_diff = (sell_price - buy_price) / buy_price;
+        let max_trade = TokenAmount::new(self.max_amount_per_trade, source_pool.decimals).to_f64();
+        let required_amount = self.calculate_optimal_amount_for_pools(source_pool, target_pool, price_diff).min(max_trade);
+        if required_amount <= 0.0 {
+            return None;
+        }
+
+        // A gap that doesn't even cover the pool fees can't survive validation
+        if price_diff * 100.0 <= self.break_even_spread(required_amount, 0.0, source_pool.fee_tier, target_pool.fee_tier) {
+            return None;
+        }
+
+        let profit_potential = required_amount * price_diff;
+        let fees = (source_pool.fee_tier + target_pool.fee_tier) as f64 / FEE_TIER_DENOMINATOR;
+        let execution_cost = required_amount * fees;
+        let min_liquidity = normalize_amount(source_pool.liquidity, source_pool.decimals)
+            .min(normalize_amount(target_pool.liquidity, target_pool.decimals));
+        let confidence = self.depth_adjusted_confidence(
+            1.0 - self.calculate_price_impact(required_amount, min_liquidity),
+            min_liquidity as u128,
+        );
+
+        Some(ArbitrageOpportunity {
+            source_pool: source_pool.clone(),
+            target_pool: target_pool.clone(),
+            profit_potential,
+            required_amount,
+            execution_cost,
+            net_profit: profit_potential - execution_cost,
+            confidence,
+            timestamp: unix_now(),
+            direction,
+            profit_potential_wei: None,
+            required_amount_wei: None,
+            execution_cost_wei: None,
+            gas_quote: None,
+            usd_prices: None,
+            native_entry: false,
+            native_exit: false,
+            urgency: Urgency::Normal,
+            max_slippage_override: None,
+        })
+    }
+
+    pub fn break_even_spread(&self, amount: f64, gas_cost_usd: f64, fee_tier_source: u32, fee_tier_target: u32) -> f64 {
+        // Percentage price gap at which amount * gap just pays both pool fees and the gas,
+        // using the same linear cost model as build_opportunity
+        if amount <= 0.0 {
+            return f64::INFINITY;
+        }
+
+        let fees = (fee_tier_source + fee_tier_target) as f64 / FEE_TIER_DENOMINATOR;
+        (fees + gas_cost_usd.max(0.0) / amount) * 100.0
+    }
+
+    pub fn find_triangular(&self, pools: &[PoolInfo]) -> Vec<ArbitrageRoute> {
+        // Single-network cycles of up to MAX_ROUTE_LENGTH pools whose price product beats the
+        // fees, and that fit within max_hops
+        let mut routes = route::find_cycles(pools);
+        routes.retain(|route| self.check_route_limits(route).is_ok());
+        routes
+    }
+
+    pub async fn recompute_net_profit(&self, opportunity: &ArbitrageOpportunity, quote: &GasQuote) -> Result<f64, ArbitrageError> {
+        // net_profit = profit_potential - execution_cost - gas for both legs and the bridge
+        let [source_wei, bridge_wei, target_wei] = self.route_gas_costs(opportunity, quote).await?;
+
+        let source_native = u256_to_f64(source_wei.saturating_add(bridge_wei)) / 1e18;
+        let target_native = u256_to_f64(target_wei) / 1e18;
+        let gas_in_token = source_native * quote.source_native_price + target_native * quote.target_native_price;
+
+        Ok(opportunity.profit_potential - opportunity.execution_cost - gas_in_token)
+    }
+
+    pub async fn route_gas_costs(&self, opportunity: &ArbitrageOpportunity, quote: &GasQuote) -> Result<[U256; 3], ArbitrageError> {
+        // Wei paid for the source leg, the bridge transfer and the target leg at the quoted
+        // gas prices, L1 data fees included
+        let (source_leg, bridge_leg, target_leg) = self.estimate_route_gas(
+            opportunity,
+            quote.source_gas_price,
+            quote.target_gas_price
+        ).await?;
+
+        let source_tx = self.leg_transaction(&opportunity.source_pool, quote.source_gas_price);
+        let target_tx = self.leg_transaction(&opportunity.target_pool, quote.target_gas_price);
+        let source_l1_fee = self.estimate_l1_data_fee(&opportunity.source_pool.network, &source_tx).await?;
+        let target_l1_fee = self.estimate_l1_data_fee(&opportunity.target_pool.network, &target_tx).await?;
+        let bridge_l1_fee = if opportunity.needs_bridge() { source_l1_fee } else { U256::zero() };
+
+        let leg_cost = |gas: U256, gas_price: U256, l1_fee: U256, network: &str| {
+            gas.checked_mul(gas_price)
+                .and_then(|cost| cost.checked_add(l1_fee))
+                .ok_or_else(|| ArbitrageError::Overflow(format!(
+                    "{} gas at {} wei on {}",
+                    gas, gas_price, network
+                )))
+        };
+
+        Ok([
+            leg_cost(source_leg, quote.source_gas_price, source_l1_fee, &opportunity.source_pool.network)?,
+            leg_cost(bridge_leg, quote.source_gas_price, bridge_l1_fee, &opportunity.source_pool.network)?,
+            leg_cost(target_leg, quote.target_gas_price, target_l1_fee, &opportunity.target_pool.network)?,
+        ])
+    }
+
+    pub async fn estimate_execution_cost(&self, opportunity: &ArbitrageOpportunity) -> Result<f64, ArbitrageError> {
+        // The cost model's execution_cost, with gas priced from the opportunity's quote when it has one
+        let gas_costs = match &opportunity.gas_quote {
+            Some(quote) => self.route_gas_costs(opportunity, quote).await?.to_vec(),
+            None => Vec::new(),
+        };
+
+        Ok(self.cost_model.estimate(opportunity, &gas_costs))
+    }
+
+    async fn estimate_route_gas(&self,
+        opportunity: &ArbitrageOpportunity,
+        source_gas_price: U256,
+        target_gas_price: U256
+    ) -> Result<(U256, U256, U256), ArbitrageError> {
+        // Gas units for the source leg, the bridge transfer and the target leg
+        let source = &opportunity.source_pool;
+        let target = &opportunity.target_pool;
+
+        let urgency = opportunity.urgency;
+        let source_leg = self.estimate_gas_cost(&source.network, &self.leg_transaction(source, source_gas_price), urgency).await?;
+        let bridge_leg = match opportunity.needs_bridge() {
+            true => self.estimate_gas_cost(&source.network, &self.leg_transaction(source, source_gas_price), urgency).await?,
+            false => U256::zero(),
+        };
+        let target_leg = self.estimate_gas_cost(&target.network, &self.leg_transaction(target, target_gas_price), urgency).await?;
+
+        Ok((source_leg, bridge_leg, target_leg))
+    }
+
+    fn leg_transaction(&self, pool: &PoolInfo, gas_price: U256) -> SecureTransaction {
+        // Placeholder swap transaction used only for gas estimation, with calldata the size
+        // of a two-token router swap so L1 data fees are priced on a realistic payload
+        SecureTransaction {
+            to: pool.address.parse().unwrap_or_default(),
+            value: U256::zero(),
+            gas_limit: U256::zero(),
+            gas_price,
+            data: vec![0xff; SWAP_CALLDATA_BYTES],
+            nonce: U256::zero(),
+            tx_type: 0,
+            max_fee_per_gas: None,
+            max_priority_fee_per_gas: None,
+        }
+    }
+
+    pub fn calculate_optimal_amount(&self, source_liquidity: u128, target_liquidity: u128, price_diff: f64) -> f64 {
+        // f64 view of the integer calculation, for display. f64 holds integers exactly only up
+        // to 2^53 (about 9e15), so amounts from larger pools come back rounded
+        self.calculate_optimal_amount_wei(source_liquidity, target_liquidity, price_diff).as_u128() as f64
+    }
+
+    pub fn calculate_optimal_amount_for_pools(&self, source: &PoolInfo, target: &PoolInfo, price_diff: f64) -> f64 {
+        // Same sizing as calculate_optimal_amount, with liquidity normalized to whole tokens so
+        // pools with different decimals compare correctly, and weighted pools discounted to
+        // the depth they actually trade at
+        let source_liquidity = normalize_amount(source.liquidity, source.decimals) * depth_factor(source.kind);
+        let target_liquidity = normalize_amount(target.liquidity, target.decimals) * depth_factor(target.kind);
+        let min_liquidity = source_liquidity.min(target_liquidity);
+        if min_liquidity <= 0.0 || !price_diff.is_finite() {
+            return 0.0;
+        }
+
+        // Stable pools barely move until far off balance, so size straight to the cap and let
+        // the slippage check in validation trim it
+        if matches!((source.kind, target.kind), (PoolKind::StableSwap { .. }, PoolKind::StableSwap { .. })) {
+            return min_liquidity * self.max_trade_fraction;
+        }
+
+        let price_multiplier = (price_diff * 10.0).clamp(self.price_multiplier_min, self.price_multiplier_max);
+
+        (min_liquidity * self.liquidity_fraction * price_multiplier).min(min_liquidity * self.max_trade_fraction)
+    }
+
+    pub fn calculate_optimal_amount_wei(&self, source_liquidity: u128, target_liquidity: u128, price_diff: f64) -> U256 {
+        self.calculate_optimal_amount_detailed(source_liquidity, target_liquidity, price_diff).amount
+    }
+
+    pub fn calculate_optimal_amount_detailed(&self, source_liquidity: u128, target_liquidity: u128, price_diff: f64) -> OptimalAmount {
+        // Calculate optimal arbitrage amount using geometric mean
+        let binding_liquidity = if target_liquidity < source_liquidity { PoolSide::Target } else { PoolSide::Source };
+        let min_liquidity = U256::from(std::cmp::min(source_liquidity, target_liquidity));
+        if min_liquidity.is_zero() || !price_diff.is_finite() {
+            return OptimalAmount { amount: U256::zero(), binding_liquidity, price_multiplier: 0.0, clamped: false };
+        }
+
+        // liquidity_fraction of minimum liquidity, in parts per million to stay in integers
+        let base_amount_ppm = U256::from((self.liquidity_fraction * 1_000_000.0) as u64);
+        
+        // Adjust based on price difference, expressed in basis points
+        let price_multiplier = (price_diff * 10.0).clamp(self.price_multiplier_min, self.price_multiplier_max);
+        let price_multiplier_bps = (price_multiplier * 10_000.0) as u64;
+        let amount = min_liquidity * base_amount_ppm * U256::from(price_multiplier_bps) / U256::from(10_000_000_000u64);
+
+        // Never propose more than max_trade_fraction of the shallower pool
+        let max_amount = min_liquidity * U256::from((self.max_trade_fraction * 1_000_000.0) as u64) / U256::from(1_000_000);
+        OptimalAmount {
+            amount: amount.min(max_amount),
+            binding_liquidity,
+            price_multiplier,
+            clamped: amount > max_amount,
+        }
+    }
+
+    pub fn depth_adjusted_confidence(&self, base: f64, liquidity: u128) -> f64 {
+        // Scale by the deepest tier the pool reaches; liquidity is in whole tokens. Pools below
+        // every tier keep base.
+        let multiplier = self.liquidity_tiers.iter()
+            .rev()
+            .find(|tier| liquidity >= u128::from(tier.min_liquidity))
+            .map_or(1.0, |tier| tier.multiplier);
+
+        (base * multiplier).clamp(0.0, 1.0)
+    }
+
+    pub fn calculate_price_impact(&self, amount: f64, liquidity: f64) -> f64 {
+        // Calculate price impact using constant product formula
+        if liquidity <= 0.0 {
+            return 1.0; // 100% impact if no liquidity
+        }
+        
+        amount / (liquidity + amount)
+    }
+
+    pub fn calculate_price_impact_v2(&self, amount_in: f64, reserve_in: f64, reserve_out: f64, fee_tier: u32) -> f64 {
+        // Realized price impact of an x*y=k swap after the pool fee is deducted
+        if reserve_in <= 0.0 || reserve_out <= 0.0 {
+            return 1.0; // 100% impact if no liquidity
+        }
+
+        if amount_in <= 0.0 {
+            return 0.0;
+        }
+
+        let amount_in_with_fee = amount_in * (FEE_TIER_DENOMINATOR - fee_tier as f64) / FEE_TIER_DENOMINATOR;
+        let amount_out = amount_in_with_fee * reserve_out / (reserve_in + amount_in_with_fee);
+
+        let spot_price = reserve_out / reserve_in;
+        let execution_price = amount_out / amount_in;
+
+        (1.0 - execution_price / spot_price).clamp(0.0, 1.0)
+    }
+
+    pub fn pool_price_impact(&self, pool: &PoolInfo, amount: f64, action: SwapAction) -> f64 {
+        // Dispatch on the pool's curve, using reserves where the curve needs them and falling
+        // back to the liquidity-only estimate when they're missing
+        let (reserve_in, reserve_out) = match action {
+            SwapAction::Sell => (pool.reserve0, pool.reserve1),
+            SwapAction::Buy => (pool.reserve1, pool.reserve0),
+        };
+        let reserves = reserve_in.zip(reserve_out)
+            .map(|(reserve_in, reserve_out)| (normalize_amount(reserve_in, pool.decimals), normalize_amount(reserve_out, pool.decimals)));
+
+        match (pool.kind, reserves) {
+            (PoolKind::StableSwap { amp }, Some((reserve_in, reserve_out))) =>
+                self.calculate_price_impact_stable(amount, reserve_in, reserve_out, amp, pool.fee_tier),
+            (PoolKind::Weighted { weight0, weight1 }, Some((reserve_in, reserve_out))) => {
+                let (weight_in, weight_out) = match action {
+                    SwapAction::Sell => (weight0, weight1),
+                    SwapAction::Buy => (weight1, weight0),
+                };
+                self.calculate_price_impact_weighted(amount, reserve_in, weight_in, reserve_out, weight_out, pool.fee_tier)
+            }
+            (PoolKind::ConcentratedV3, _) => {
+                // Single-tick estimate: no tick data, so the swap may run to any price
+                let sqrt_price_x96 = denormalize_amount(pool.price.sqrt() * 2f64.powi(96), 0);
+                let zero_for_one = action == SwapAction::Sell;
+                let sqrt_price_limit_x96 = if zero_for_one { U256::zero() } else { U256::MAX };
+                self.calculate_price_impact_v3(
+                    sqrt_price_x96,
+                    pool.liquidity,
+                    denormalize_amount(amount, pool.decimals),
+                    zero_for_one,
+                    sqrt_price_limit_x96,
+                    pool.fee_tier
+                ).map(|estimate| estimate.price_impact).unwrap_or(1.0)
+            }
+            (_, Some((reserve_in, reserve_out))) => self.calculate_price_impact_v2(amount, reserve_in, reserve_out, pool.fee_tier),
+            (_, None) => self.calculate_price_impact(amount, normalize_amount(pool.liquidity, pool.decimals)),
+        }
+    }
+
+    pub fn calculate_price_impact_fixed(&self, amount: U256, liquidity: u128) -> U256 {
+        // calculate_price_impact on raw integers, scaled by PRICE_IMPACT_SCALE, so pools
+        // deeper than 2^53 don't lose the precision a borderline slippage check depends on
+        let scale = U256::from(PRICE_IMPACT_SCALE);
+        if liquidity == 0 {
+            return scale;
+        }
+
+        let total = U512::from(liquidity) + U512::from(amount);
+        U256::try_from(amount.full_mul(scale) / total).unwrap_or(scale)
+    }
+
+    pub fn calculate_price_impact_v2_fixed(&self, amount_in: U256, reserve_in: u128, reserve_out: u128, fee_tier: u32) -> U256 {
+        // calculate_price_impact_v2 on raw integers, scaled by PRICE_IMPACT_SCALE. Execution
+        // over spot price reduces to fee * reserve_in / (reserve_in + amount_in * fee)
+        let scale = U256::from(PRICE_IMPACT_SCALE);
+        if reserve_in == 0 || reserve_out == 0 {
+            return scale;
+        }
+        if amount_in.is_zero() {
+            return U256::zero();
+        }
+
+        let denominator = U256::from(FEE_TIER_DENOMINATOR as u64);
+        let fee = denominator.saturating_sub(U256::from(fee_tier));
+        let amount_in_with_fee = amount_in.full_mul(fee) / U512::from(denominator);
+        let ratio = U512::from(scale) * U512::from(fee) * U512::from(reserve_in)
+            / (U512::from(denominator) * (U512::from(reserve_in) + amount_in_with_fee));
+
+        scale.saturating_sub(U256::try_from(ratio).unwrap_or(scale))
+    }
+
+    pub fn pool_price_impact_fixed(&self, pool: &PoolInfo, amount: U256, action: SwapAction) -> U256 {
+        // Integer counterpart of pool_price_impact for the constant product curve, which is
+        // what validation decides on; the other curves are only as precise as their f64 math
+        let (reserve_in, reserve_out) = match action {
+            SwapAction::Sell => (pool.reserve0, pool.reserve1),
+            SwapAction::Buy => (pool.reserve1, pool.reserve0),
+        };
+
+        match (pool.kind, reserve_in.zip(reserve_out)) {
+            (PoolKind::ConstantProduct, Some((reserve_in, reserve_out))) =>
+                self.calculate_price_impact_v2_fixed(amount, reserve_in, reserve_out, pool.fee_tier),
+            (PoolKind::ConstantProduct, None) => self.calculate_price_impact_fixed(amount, pool.liquidity),
+            _ => {
+                let impact = self.pool_price_impact(pool, TokenAmount::new(amount, pool.decimals).to_f64(), action);
+                denormalize_amount(impact, 18)
+            }
+        }
+    }
+
+    pub fn calculate_stable_swap_out(&self, amount_in: f64, reserve_in: f64, reserve_out: f64, amp: u64, fee_tier: u32) -> f64 {
+        // Curve get_dy for a two-coin pool, net of the fee
+        stable_swap::get_dy(amount_in, reserve_in, reserve_out, amp, fee_tier)
+    }
+
+    pub fn calculate_price_impact_stable(&self, amount_in: f64, reserve_in: f64, reserve_out: f64, amp: u64, fee_tier: u32) -> f64 {
+        // StableSwap counterpart of calculate_price_impact_v2, fee included
+        stable_swap::price_impact(amount_in, reserve_in, reserve_out, amp, fee_tier)
+    }
+
+    pub fn calculate_weighted_out(&self, amount_in: f64, reserve_in: f64, weight_in: f64, reserve_out: f64, weight_out: f64, fee_tier: u32) -> f64 {
+        // Balancer outGivenIn for a two-token weighted pool, net of the fee
+        weighted::out_given_in(amount_in, reserve_in, weight_in, reserve_out, weight_out, fee_tier)
+    }
+
+    pub fn calculate_price_impact_weighted(&self, amount_in: f64, reserve_in: f64, weight_in: f64, reserve_out: f64, weight_out: f64, fee_tier: u32) -> f64 {
+        // Weighted counterpart of calculate_price_impact_v2, fee included
+        weighted::price_impact(amount_in, reserve_in, weight_in, reserve_out, weight_out, fee_tier)
+    }
+
+    pub fn sandwich_risk(&self, amount: f64, reserve_in: f64, reserve_out: f64, fee_tier: u32) -> f64 {
+        // 0..1 exposure to a sandwich: near 0 while the trade's price impact is small next to
+        // the two pool fees an attacker pays, near 1 once the impact dwarfs them
+        let price_impact = self.calculate_price_impact_v2(amount, reserve_in, reserve_out, fee_tier);
+        sandwich_risk_from_impact(price_impact, fee_tier)
+    }
+
+    pub fn calculate_price_impact_v3(&self,
+        sqrt_price_x96: U256,
+        liquidity: u128,
+        amount_in: U256,
+        zero_for_one: bool,
+        sqrt_price_limit_x96: U256,
+        fee_tier: u32
+    ) -> Result<V3SwapEstimate, ArbitrageError> {
+        // Uniswap V3 swap within a single tick: the active liquidity is assumed constant
+        // for the whole swap, so tick crossings are only flagged, not simulated
+        if liquidity == 0 || sqrt_price_x96.is_zero() {
+            return Err(ArbitrageError::ValidationFailed("pool has no active liquidity".to_string()));
+        }
+
+        let fee_factor = U256::from((FEE_TIER_DENOMINATOR as u64).saturating_sub(fee_tier as u64));
+        let amount_in_less_fee = amount_in.full_mul(fee_factor) / U512::from(FEE_TIER_DENOMINATOR as u64);
+
+        let sqrt_price = U512::from(sqrt_price_x96);
+        let liquidity = U512::from(liquidity);
+        let liquidity_x96 = liquidity << 96;
+
+        let (sqrt_price_next, amount_out) = if zero_for_one {
+            // Token0 in: sqrtP' = L * sqrtP / (L + amount * sqrtP), output dy = L * (sqrtP - sqrtP')
+            let next = liquidity_x96 * sqrt_price / (liquidity_x96 + amount_in_less_fee * sqrt_price);
+            let out = (liquidity * (sqrt_price - next)) >> 96;
+            (next, out)
+        } else {
+            // Token1 in: sqrtP' = sqrtP + amount / L, output dx = L * (1/sqrtP - 1/sqrtP')
+            let next = sqrt_price + (amount_in_less_fee << 96) / liquidity;
+            let out = liquidity_x96 * (next - sqrt_price) / (next * sqrt_price);
+            (next, out)
+        };
+
+        let to_u256 = |value: U512| U256::try_from(value)
+            .map_err(|_| ArbitrageError::ValidationFailed("V3 swap math overflowed".to_string()));
+        let sqrt_price_next_x96 = to_u256(sqrt_price_next)?;
+        let amount_out = to_u256(amount_out)?;
+
+        let crosses_tick = if zero_for_one {
+            sqrt_price_next_x96 < sqrt_price_limit_x96
+        } else {
+            sqrt_price_next_x96 > sqrt_price_limit_x96
+        };
+
+        // Compare the execution price against the spot price in the input token's terms
+        let spot_price = (u256_to_f64(sqrt_price_x96) / 2f64.powi(96)).powi(2);
+        let spot_price = if zero_for_one { spot_price } else { 1.0 / spot_price };
+        let price_impact = if amount_in.is_zero() {
+            0.0
+        } else {
+            let execution_price = u256_to_f64(amount_out) / u256_to_f64(amount_in);
+            (1.0 - execution_price / spot_price).clamp(0.0, 1.0)
+        };
+
+        Ok(V3SwapEstimate {
+            sqrt_price_next_x96,
+            amount_out,
+            price_impact,
+            crosses_tick,
+        })
+    }
+
+    pub async fn suggest_gas_price(&self, network: &str) -> Result<GasSuggestion, ArbitrageError> {
+        // Base fee plus median priority fee from recent blocks, cached briefly per network
+        let rpc_url = &self.network(network)?.rpc_url;
+        if let Some(suggestion) = self.gas_oracle.cached(network) {
+            return Ok(suggestion);
+        }
+
+        self.rate_limiter.acquire(network).await;
+        self.with_rpc_timeout(network, self.gas_oracle.suggest(network, rpc_url)).await?
+    }
+
+    pub fn gas_multiplier(&self, network: &str, urgency: Urgency) -> f64 {
+        self.gas_multipliers.get(network, urgency)
+    }
+
+    pub fn gas_limit_band(&self, network: &str) -> GasLimitBand {
+        // The network's own band when it has one
+        self.networks.get(network)
+            .and_then(|config| config.gas_limit_band)
+            .unwrap_or(self.gas_limit_band)
+    }
+
+    pub async fn estimate_gas_cost(&self, network: &str, _transaction: &SecureTransaction, urgency: Urgency) -> Result<U256, ArbitrageError> {
+        // Estimate gas cost with safety multiplier
+        let estimated_gas = U256::from(self.network(network)?.base_gas);
+        
+        // Apply safety multiplier. A u64 of gas times at most MAX_GAS_MULTIPLIER always fits.
+        let multiplier = self.gas_multiplier(network, urgency);
+        let safe_gas = estimated_gas * U256::from((multiplier * 100.0) as u64) / U256::from(100);
+        
+        Ok(safe_gas)
+    }
+
+    pub async fn estimate_gas_cost_usd(&self,
+        network: &str,
+        tx: &SecureTransaction,
+        native_price_usd: f64,
+        urgency: Urgency
+    ) -> Result<f64, ArbitrageError> {
+        // gas * gas_price is in wei of the network's native token, as is any L1 data fee
+        let gas = self.estimate_gas_cost(network, tx, urgency).await?;
+        let l1_fee = self.estimate_l1_data_fee(network, tx).await?;
+        let cost_wei = gas.checked_mul(tx.gas_price)
+            .and_then(|cost| cost.checked_add(l1_fee))
+            .ok_or_else(|| ArbitrageError::Overflow(format!(
+                "{} gas at {} wei on {}",
+                gas, tx.gas_price, network
+            )))?;
+
+        Ok(u256_to_f64(cost_wei) * native_price_usd / 1e18)
+    }
+
+    pub async fn estimate_l1_data_fee(&self, network: &str, tx: &SecureTransaction) -> Result<U256, ArbitrageError> {
+        // Wei charged for posting the transaction to L1; zero off the OP stack
+        let config = self.network(network)?;
+        if !config.op_stack {
+            return Ok(U256::zero());
+        }
+
+        let l1_base_fee = match self.gas_oracle.cached_l1_base_fee(network) {
+            Some(fee) => fee,
+            None => {
+                self.rate_limiter.acquire(network).await;
+                self.with_rpc_timeout(network, self.gas_oracle.l1_base_fee(network, &config.rpc_url)).await??
+            }
+        };
+
+        Ok(l1_data_gas(&tx.data).saturating_mul(l1_base_fee))
+    }
+
+    pub async fn estimate_total_gas_cost_usd(&self,
+        opportunity: &ArbitrageOpportunity,
+        quote: &GasQuote,
+        prices: &UsdPrices
+    ) -> Result<f64, ArbitrageError> {
+        // Source leg and bridge are paid on the source network, the target leg on the target network
+        let source = &opportunity.source_pool;
+        let target = &opportunity.target_pool;
+        let source_tx = self.leg_transaction(source, quote.source_gas_price);
+        let target_tx = self.leg_transaction(target, quote.target_gas_price);
+
+        let urgency = opportunity.urgency;
+        let source_leg = self.estimate_gas_cost_usd(&source.network, &source_tx, prices.source_native, urgency).await?;
+        let bridge_leg = match opportunity.needs_bridge() {
+            true => self.estimate_gas_cost_usd(&source.network, &source_tx, prices.source_native, urgency).await?,
+            false => 0.0,
+        };
+        let target_leg = self.estimate_gas_cost_usd(&target.network, &target_tx, prices.target_native, urgency).await?;
+
+        Ok(source_leg + bridge_leg + target_leg)
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    pub fn secure_transaction_builder(&self,
+        network: &str,
+        to: &str, 
+        value: u64, 
+        data: Vec<u8>,
+        gas_limit: u64,
+        gas_price: u64,
+        nonce: u64
+    ) -> Result<SecureTransaction, ArbitrageError> {
+        // Build transaction with security validations
+        
+        let to_address = self.validate_transaction_params(network, to, gas_limit, gas_price)?;
+
+        Ok(SecureTransaction {
+            to: to_address,
+            value: U256::from(value),
+            gas_limit: U256::from(gas_limit),
+            gas_price: U256::from(gas_price),
+            data,
+            nonce: U256::from(nonce),
+            tx_type: 0,
+            max_fee_per_gas: None,
+            max_priority_fee_per_gas: None,
+        })
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    pub fn secure_transaction_builder_1559(&self,
+        network: &str,
+        to: &str,
+        value: u64,
+        data: Vec<u8>,
+        gas_limit: u64,
+        max_fee_per_gas: u64,
+        max_priority_fee_per_gas: u64,
+        nonce: u64
+    ) -> Result<SecureTransaction, ArbitrageError> {
+        // Build a type-2 transaction; the legacy gas price ceiling applies to the max fee
+        let to_address = self.validate_transaction_params(network, to, gas_limit, max_fee_per_gas)?;
+
+        if max_priority_fee_per_gas > max_fee_per_gas {
+            return Err(ArbitrageError::ValidationFailed(format!(
+                "priority fee {} exceeds max fee {}",
+                max_priority_fee_per_gas, max_fee_per_gas
+            )));
+        }
+
+        Ok(SecureTransaction {
+            to: to_address,
+            value: U256::from(value),
+            gas_limit: U256::from(gas_limit),
+            gas_price: U256::from(max_fee_per_gas),
+            data,
+            nonce: U256::from(nonce),
+            tx_type: 2,
+            max_fee_per_gas: Some(U256::from(max_fee_per_gas)),
+            max_priority_fee_per_gas: Some(U256::from(max_priority_fee_per_gas)),
+        })
+    }
+
+    fn validate_transaction_params(&self, network: &str, to: &str, gas_limit: u64, gas_price: u64) -> Result<Address, ArbitrageError> {
+        let to_address = to.parse::<Address>()
+            .map_err(|_| ArbitrageError::InvalidAddress(to.to_string()))?;
+        
+        // Validate gas parameters
+        self.gas_limit_band(network).check(gas_limit)?;
+        
+        if gas_price > MAX_GAS_PRICE_WEI {
+            return Err(ArbitrageError::GasPriceTooHigh(gas_price));
+        }
+
+        Ok(to_address)
+    }
+
+    pub async fn execute_same_chain_arbitrage(&self,
+        opportunity: ArbitrageOpportunity,
+        cancel: &CancellationToken
+    ) -> Result<ExecutionResult, ArbitrageError> {
+        // Both legs on one network, each through its own DEX router, with no bridge in between
+        if opportunity.needs_bridge() {
+            return Err(ArbitrageError::ValidationFailed(format!(
+                "pools are on {} and {}, not one network",
+                opportunity.source_pool.network, opportunity.target_pool.network
+            )));
+        }
+
+        self.execute_cross_chain_arbitrage(opportunity, cancel).await
+    }
+
+    #[tracing::instrument(
+        name = "cross_chain_arbitrage",
+        skip(self, opportunity),
+        fields(
+            source_network = %opportunity.source_pool.network,
+            target_network = %opportunity.target_pool.network,
+            net_profit = opportunity.net_profit,
+        )
+    )]
+    pub async fn execute_cross_chain_arbitrage(&self,
+        opportunity: ArbitrageOpportunity,
+        cancel: &CancellationToken
+    ) -> Result<ExecutionResult, ArbitrageError> {
+        // Execute cross-chain arbitrage with enhanced security.
+        // Cancelling stops the run with Cancelled at any point up to submitting the source
+        // trade: before validation, before the gas check, before the dry run or capital
+        // reservation, and before a native entry is wrapped and the source swap is signed and
+        // sent. From there on funds are committed, so wrapping, the source trade and its
+        // confirmation, the bridge and its confirmation, the target trade and any unwrap
+        // always run to completion and the token is ignored. The kill switch is checked here
+        // only, with the same effect on executions already running.
+        self.check_halted()?;
+        self.circuit_breaker.check()?;
+
+        // A retried call for a trade that already ran gets the earlier result back
+        let guard = match self.executions.begin(&opportunity.opportunity_id())? {
+            dedup::Admission::Started(guard) => guard,
+            dedup::Admission::Completed(result) => return Ok(result),
+        };
+
+        // Retries from every call below draw on one budget, so a dying opportunity can't keep
+        // retrying past its window
+        let retries = ExecutionRetries::new(self.retry_budget, self.retry_budget_window);
+        let result = EXECUTION_RETRIES.scope(retries, self.attempt_arbitrage(&opportunity, cancel)).await;
+        guard.finish(&result);
+        match &result {
+            Ok(result) => self.observe(|observer| observer.on_completed(&opportunity, result)),
+            Err(err) => self.observe(|observer| observer.on_failed(&opportunity, err)),
+        }
+        if let Some(journal) = &self.journal {
+            journal.record(&opportunity, &result).await;
+        }
+
+        result
+    }
+
+    async fn attempt_arbitrage(&self, opportunity: &ArbitrageOpportunity, cancel: &CancellationToken) -> Result<ExecutionResult, ArbitrageError> {
+        // Validate opportunity first
+        check_cancelled(cancel)?;
+        if !self.validate_opportunity(opportunity).await? {
+            return Err(ArbitrageError::ValidationFailed("opportunity rejected by security checks".to_string()));
+        }
+        self.observe(|observer| observer.on_validated(opportunity));
+
+        // Recompute the execution cost with the cost model instead of trusting the caller's,
+        // oracle prices filled in when the opportunity has a gas quote but no USD prices, and
+        // require what is left to clear min_net_profit
+        check_cancelled(cancel)?;
+        let mut opportunity = opportunity.clone();
+        if opportunity.gas_quote.is_some() && opportunity.usd_prices.is_none() {
+            opportunity.usd_prices = self.oracle_usd_prices(&opportunity).await;
+        }
+        let execution_cost = self.estimate_execution_cost(&opportunity).await?;
+        opportunity.execution_cost = execution_cost;
+        opportunity.execution_cost_wei = opportunity.execution_cost_wei
+            .map(|cost| TokenAmount::new(denormalize_amount(execution_cost, cost.decimals), cost.decimals));
+        opportunity.net_profit = opportunity.profit_potential - execution_cost;
+        if opportunity.net_profit <= self.min_net_profit {
+            return Err(ArbitrageError::ValidationFailed(format!(
+                "profit {:.6} does not cover execution cost {:.6}",
+                opportunity.profit_potential, execution_cost
+            )));
+        }
+
+        // Dry run: report the plan and stop before anything is submitted
+        check_cancelled(cancel)?;
+        if self.dry_run {
+            return self.plan_dry_run(&opportunity).await;
+        }
+
+        self.reserve_capital(opportunity.required_amount_raw())?;
+
+        // Only failures after validation count towards opening the circuit
+        let result = self.execute_legs(&opportunity, cancel).await;
+        match &result {
+            Ok(_) => self.circuit_breaker.record_success(),
+            // Nothing was submitted, so a cancellation says nothing about the trade
+            Err(ArbitrageError::Cancelled) => {}
+            Err(_) => self.circuit_breaker.record_failure(),
+        }
+
+        result
+    }
+
+    fn reserve_capital(&self, amount: U256) -> Result<(), ArbitrageError> {
+        // Counts towards the cycle's capital whether or not the trade then succeeds
+        let mut deployed = self.deployed_capital.lock().unwrap();
+        let available = self.max_capital_per_cycle.saturating_sub(*deployed);
+        if amount > available {
+            return Err(ArbitrageError::CapitalLimitExceeded { requested: amount, available });
+        }
+
+        *deployed += amount;
+        Ok(())
+    }
+
+    pub fn deployed_capital(&self) -> U256 {
+        *self.deployed_capital.lock().unwrap()
+    }
+
+    async fn execute_legs(&self, opportunity: &ArbitrageOpportunity, cancel: &CancellationToken) -> Result<ExecutionResult, ArbitrageError> {
+        // The target leg only runs after the bridge lands, so its deadline covers the bridge ETA.
+        // Single-network opportunities skip the bridge, and with concurrent_legs run both swaps
+        // together.
+        let started_at = unix_now();
+        let source_deadline = started_at + self.deadline_buffer.as_secs();
+        let bridge_eta = self.bridge
+            .estimate_bridge_time(&opportunity.source_pool.network, &opportunity.target_pool.network)
+            .await;
+        let target_deadline = match opportunity.needs_bridge() {
+            true => source_deadline + bridge_eta.as_secs(),
+            false => source_deadline,
+        };
+
+        self.check_native_route(opportunity)?;
+
+        // Step 1: Wrap a native entry, then execute the source trade. This is the last point
+        // a cancellation is honoured.
+        check_cancelled(cancel)?;
+        let requested = opportunity.required_amount_raw();
+        let source_network = &opportunity.source_pool.network;
+        let mut leg_hashes = Vec::new();
+        let mut wrap_gas = U256::zero();
+        if opportunity.native_entry {
+            // Anything a partial fill leaves unspent stays wrapped
+            let wrap_tx_hash = self.wrap_native(source_network, requested).await?;
+            wrap_gas = self.wait_for_confirmation(&wrap_tx_hash, source_network, source_network, self.deadline_buffer)
+                .instrument(info_span!("wrap", tx_hash = %wrap_tx_hash))
+                .await?;
+            leg_hashes.push(wrap_tx_hash);
+        }
+
+        // Balances once the entry token is in the wallet, for the realized profit
+        let balances_before = self.route_balances(opportunity).await;
+        let legs = if self.concurrent_legs && !opportunity.needs_bridge() {
+            self.run_legs_concurrently(opportunity, requested, source_deadline).await?
+        } else {
+            self.run_legs_sequentially(opportunity, requested, source_deadline, target_deadline).await?
+        };
+        let SettledLegs { source_fill, source_gas, bridge_tx_hash, bridge_gas, target_fill, target_requested, target_gas } = legs;
+        let target_network = &opportunity.target_pool.network;
+
+        // Each leg against what it was sized for, in its own token; a partial fill on either
+        // shrinks the share of the opportunity that was captured
+        let filled = source_fill.filled;
+        let fill_ratio = leg_fill_ratio(filled, requested) * leg_fill_ratio(target_fill.filled, target_requested);
+        let balances_after = match balances_before {
+            Some(_) => self.route_balances(opportunity).await,
+            None => None,
+        };
+        let balances = balances_before.zip(balances_after);
+        leg_hashes.extend([&source_fill.tx_hash, &bridge_tx_hash, &target_fill.tx_hash].into_iter()
+            .filter(|tx_hash| !tx_hash.is_empty())
+            .cloned());
+
+        // Step 5: Unwrap a native exit. The trade has settled by now, so a failure here only
+        // leaves the proceeds wrapped and is logged rather than returned.
+        let mut unwrap_gas = U256::zero();
+        if opportunity.native_exit {
+            match balances {
+                Some(((_, end_before), (_, end_after))) if end_after > end_before => {
+                    let unwrapped = async {
+                        let tx_hash = self.unwrap_native(target_network, end_after - end_before).await?;
+                        let gas = self.wait_for_confirmation(&tx_hash, target_network, target_network, self.deadline_buffer).await?;
+                        Ok::<_, ArbitrageError>((tx_hash, gas))
+                    };
+                    match unwrapped.instrument(info_span!("unwrap")).await {
+                        Ok((tx_hash, gas)) => {
+                            leg_hashes.push(tx_hash);
+                            unwrap_gas = gas;
+                        }
+                        Err(err) => tracing::warn!(network = %target_network, error = %err, "unwrap failed, proceeds left wrapped"),
+                    }
+                }
+                _ => tracing::warn!(network = %target_network, "target proceeds unknown, leaving them wrapped"),
+            }
+        }
+
+        let report = execution_report(
+            opportunity,
+            fill_ratio,
+            [wrap_gas + source_gas + bridge_gas, target_gas + unwrap_gas],
+            balances,
+            leg_hashes
+        );
+        self.metrics.record_executed(report.realized_net_profit);
+
+        Ok(ExecutionResult::Executed {
+            source_tx_hash: source_fill.tx_hash,
+            bridge_tx_hash,
+            target_tx_hash: target_fill.tx_hash,
+            filled,
+            requested,
+            report,
+        })
+    }
+
+    async fn run_legs_sequentially(&self,
+        opportunity: &ArbitrageOpportunity,
+        requested: U256,
+        source_deadline: u64,
+        target_deadline: u64
+    ) -> Result<SettledLegs, ArbitrageError> {
+        // Source trade, bridge unless both pools share a network, then the target trade with
+        // whatever the source leg filled and the bridge delivered
+        let source_network = &opportunity.source_pool.network;
+        let source_fill = traced_stage(info_span!("source_trade", tx_hash = field::Empty, duration_ms = field::Empty),
+            self.execute_trade(
+                &opportunity.source_pool,
+                requested,
+                opportunity.direction.source_action(),
+                source_deadline,
+                self.max_slippage_for(opportunity)
+            )
+        ).await?;
+        self.observe(|observer| observer.on_source_submitted(opportunity, &source_fill.tx_hash));
+
+        // A reverted source swap leaves nothing to bridge, so stop before the other legs
+        let source_gas = self.wait_for_confirmation(&source_fill.tx_hash, source_network, source_network, self.deadline_buffer)
+            .instrument(info_span!("source_confirmation", tx_hash = %source_fill.tx_hash))
+            .await?;
+        self.observe(|observer| observer.on_confirmed(opportunity, ExecutionStage::Source, &source_fill.tx_hash));
+
+        // What the source swap bought: the bridged token, and what the target leg sells
+        let bought = executor::quote_amount_out(
+            source_fill.filled,
+            opportunity.source_pool.price,
+            opportunity.direction.source_action()
+        );
+
+        let (bridge_tx_hash, bridge_gas, delivered) = if opportunity.needs_bridge() {
+            // Step 2: Bridge tokens. The target leg is sized by what the bridge delivers, not
+            // by what was sent.
+            let token = opportunity.bridged_token().parse::<Address>()
+                .map_err(|_| ArbitrageError::InvalidAddress(opportunity.bridged_token().to_string()))?;
+            let quote = self.quote_bridge(
+                &opportunity.source_pool.network,
+                &opportunity.target_pool.network,
+                token,
+                bought
+            ).await?;
+            tracing::info!(sent = %bought, amount_out = %quote.amount_out, fee = %quote.fee, "bridge quote");
+            let bridge_tx_hash = traced_stage(info_span!("bridge", tx_hash = field::Empty, duration_ms = field::Empty),
+                self.bridge_tokens(
+                    &opportunity.source_pool.network,
+                    &opportunity.target_pool.network,
+                    token,
+                    bought
+                )
+            ).await?;
+            self.observe(|observer| observer.on_bridge_submitted(opportunity, &bridge_tx_hash));
+
+            // Step 3: Wait for bridge confirmation
+            let confirmation_span = info_span!("confirmation", tx_hash = %bridge_tx_hash, duration_ms = field::Empty);
+            let started = std::time::Instant::now();
+            let timeout = self.bridge_timeout_for(&opportunity.source_pool.network, &opportunity.target_pool.network);
+            let route = format!("{}->{}", opportunity.source_pool.network, opportunity.target_pool.network);
+            let confirmation = self.wait_for_confirmation(&bridge_tx_hash, &opportunity.source_pool.network, &route, timeout)
+                .instrument(confirmation_span.clone())
+                .await;
+            confirmation_span.record("duration_ms", started.elapsed().as_millis() as u64);
+            if let Err(ArbitrageError::ConfirmationTimeout { .. }) = &confirmation {
+                self.metrics.record_bridge_timeout();
+            }
+            let bridge_gas = confirmation?;
+            self.observe(|observer| observer.on_confirmed(opportunity, ExecutionStage::Bridge, &bridge_tx_hash));
+            (bridge_tx_hash, bridge_gas, quote.amount_out)
+        } else {
+            (String::new(), U256::zero(), bought)
+        };
+
+        // Step 4: Execute target trade, sized down again if its pool has drained since
+        let target_fill = traced_stage(info_span!("target_trade", tx_hash = field::Empty, duration_ms = field::Empty),
+            self.execute_trade(
+                &opportunity.target_pool,
+                delivered,
+                opportunity.direction.target_action(),
+                target_deadline,
+                self.max_slippage_for(opportunity)
+            )
+        ).await?;
+        self.observe(|observer| observer.on_target_submitted(opportunity, &target_fill.tx_hash));
+
+        // The report needs the target leg's gas
+        let target_network = &opportunity.target_pool.network;
+        let target_gas = self.wait_for_confirmation(&target_fill.tx_hash, target_network, target_network, self.deadline_buffer)
+            .instrument(info_span!("target_confirmation", tx_hash = %target_fill.tx_hash))
+            .await?;
+        self.observe(|observer| observer.on_confirmed(opportunity, ExecutionStage::Target, &target_fill.tx_hash));
+
+        Ok(SettledLegs { source_fill, source_gas, bridge_tx_hash, bridge_gas, target_fill, target_requested: delivered, target_gas })
+    }
+
+    async fn run_legs_concurrently(&self,
+        opportunity: &ArbitrageOpportunity,
+        requested: U256,
+        deadline: u64
+    ) -> Result<SettledLegs, ArbitrageError> {
+        // Both swaps on one network at once, shrinking the window in which prices can move
+        // between them. The target leg sells inventory already in the wallet rather than the
+        // source leg's output; the nonce manager gives each swap its own nonce.
+        let network = &opportunity.source_pool.network;
+        let max_slippage = self.max_slippage_for(opportunity);
+        // Sized as the source leg's quoted output, the amount it would have handed over
+        let bought = executor::quote_amount_out(requested, opportunity.source_pool.price, opportunity.direction.source_action());
+        let (source_fill, target_fill) = tokio::join!(
+            traced_stage(info_span!("source_trade", tx_hash = field::Empty, duration_ms = field::Empty),
+                self.execute_trade(&opportunity.source_pool, requested, opportunity.direction.source_action(), deadline, max_slippage)
+            ),
+            traced_stage(info_span!("target_trade", tx_hash = field::Empty, duration_ms = field::Empty),
+                self.execute_trade(&opportunity.target_pool, bought, opportunity.direction.target_action(), deadline, max_slippage)
+            ),
+        );
+        let (source_fill, target_fill) = match (source_fill, target_fill) {
+            (Ok(source_fill), Ok(target_fill)) => (source_fill, target_fill),
+            (Err(err), Ok(submitted)) | (Ok(submitted), Err(err)) => {
+                tracing::warn!(%network, tx_hash = %submitted.tx_hash, error = %err, "one concurrent leg failed, the other was submitted");
+                return Err(err);
+            }
+            (Err(err), Err(_)) => return Err(err),
+        };
+        self.observe(|observer| {
+            observer.on_source_submitted(opportunity, &source_fill.tx_hash);
+            observer.on_target_submitted(opportunity, &target_fill.tx_hash);
+        });
+
+        let (source_gas, target_gas) = futures::future::try_join(
+            self.wait_for_confirmation(&source_fill.tx_hash, network, network, self.deadline_buffer)
+                .instrument(info_span!("source_confirmation", tx_hash = %source_fill.tx_hash)),
+            self.wait_for_confirmation(&target_fill.tx_hash, network, network, self.deadline_buffer)
+                .instrument(info_span!("target_confirmation", tx_hash = %target_fill.tx_hash)),
+        ).await?;
+        self.observe(|observer| {
+            observer.on_confirmed(opportunity, ExecutionStage::Source, &source_fill.tx_hash);
+            observer.on_confirmed(opportunity, ExecutionStage::Target, &target_fill.tx_hash);
+        });
+
+        Ok(SettledLegs {
+            source_fill,
+            source_gas,
+            bridge_tx_hash: String::new(),
+            bridge_gas: U256::zero(),
+            target_fill,
+            target_requested: bought,
+            target_gas,
+        })
+    }
+
+    fn check_native_route(&self, opportunity: &ArbitrageOpportunity) -> Result<(), ArbitrageError> {
+        // A native entry or exit only works when the route's token there is the wrapped native
+        let ends = [
+            (opportunity.native_entry, &opportunity.source_pool, opportunity.direction.source_action(), 0),
+            (opportunity.native_exit, &opportunity.target_pool, opportunity.direction.target_action(), 1),
+        ];
+        for (native, pool, action, end) in ends {
+            if !native {
+                continue;
+            }
+            let (_, wrapped) = self.wrapping(&pool.network)?;
+            let token = swap_path(pool, action)?[end];
+            if token != wrapped {
+                return Err(ArbitrageError::ValidationFailed(format!(
+                    "native route on {} needs {:?}, pool trades {:?}",
+                    pool.network, wrapped, token
+                )));
+            }
+        }
+
+        Ok(())
+    }
+
+    async fn route_balances(&self, opportunity: &ArbitrageOpportunity) -> Option<(U256, U256)> {
+        // The signer's balance of the token the route starts with on the source network and
+        // ends with on the target network. None when there is no signer or a read fails.
+        let owner = self.signer.as_ref()?.address();
+        let [start_token, _] = swap_path(&opportunity.source_pool, opportunity.direction.source_action()).ok()?;
+        let [_, end_token] = swap_path(&opportunity.target_pool, opportunity.direction.target_action()).ok()?;
+        let balance = |network: &str, token: Address| {
+            let network = network.to_string();
+            async move {
+                let rpc_url = &self.network(&network)?.rpc_url;
+                self.rate_limiter.acquire(&network).await;
+                self.with_rpc_timeout(&network, allowance::read_balance(rpc_url, token, owner)).await?
+            }
+        };
+
+        let balances = futures::future::try_join(
+            balance(&opportunity.source_pool.network, start_token),
+            balance(&opportunity.target_pool.network, end_token),
+        ).await;
+        match balances {
+            Ok(balances) => Some(balances),
+            Err(err) => {
+                tracing::warn!(error = %err, "cannot read route balances, reporting estimated profit");
+                None
+            }
+        }
+    }
+
+    pub async fn execute_route(&self, route: &ArbitrageRoute, amount_in: U256) -> Result<ExecutionResult, ArbitrageError> {
+        // Run the hops of a multi-hop route in order, feeding each hop's minimum output into the next
+        self.circuit_breaker.check()?;
+        self.validate_route(route)?;
+
+        if self.dry_run {
+            return self.plan_route_dry_run(route, amount_in).await;
+        }
+
+        let result = self.execute_hops(route, amount_in).await;
+        match &result {
+            Ok(_) => self.circuit_breaker.record_success(),
+            Err(_) => self.circuit_breaker.record_failure(),
+        }
+
+        result
+    }
+
+    pub async fn execute_flash_arbitrage(&self, route: ArbitrageRoute) -> Result<ExecutionResult, ArbitrageError> {
+        // Fund a single-network route with a flash loan so no capital has to be held up front
+        self.circuit_breaker.check()?;
+        self.validate_route(&route)?;
+
+        let provider = self.flash_loan.as_ref()
+            .ok_or_else(|| ArbitrageError::InvalidConfig("no flash loan provider configured".to_string()))?;
+        if !route.is_single_network() {
+            return Err(ArbitrageError::ValidationFailed("flash loans cannot span a bridge".to_string()));
+        }
+
+        let token = route.start_token.parse::<Address>()
+            .map_err(|_| ArbitrageError::InvalidAddress(route.start_token.clone()))?;
+        let loan_amount = self.route_amount(&route);
+        let loan_fee = provider.loan_fee(loan_amount);
+
+        // Chain each hop's minimum output into the next, so the last one is what repayment can rely on
+        let deadline = unix_now() + self.deadline_buffer.as_secs();
+        let mut amount = loan_amount;
+        let mut calls = Vec::with_capacity(route.pools.len());
+        for (hop, pool) in route.pools.iter().enumerate() {
+            let request = self.build_swap_request(pool, amount, route.actions[hop], deadline, self.max_slippage);
+            let path = [route.token_in(hop), route.token_out(hop)].iter()
+                .map(|token| token.parse::<Address>().map_err(|_| ArbitrageError::InvalidAddress(token.to_string())))
+                .collect::<Result<Vec<_>, _>>()?;
+
+            calls.push(Token::Tuple(vec![
+                Token::Address(self.router_for(pool)?),
+                Token::Bytes(request.encode_router_calldata(&path, provider.receiver())),
+            ]));
+            amount = request.amount_out_min;
+        }
+
+        if amount <= loan_amount.saturating_add(loan_fee) {
+            return Err(ArbitrageError::ValidationFailed(format!(
+                "minimum output {} does not repay loan {} plus fee {}",
+                amount, loan_amount, loan_fee
+            )));
+        }
+
+        if self.dry_run {
+            return self.plan_route_dry_run(&route, loan_amount).await;
+        }
+
+        let callback_data = ethabi::encode(&[Token::Array(calls)]);
+        let result = traced_stage(info_span!("flash_loan", tx_hash = field::Empty, duration_ms = field::Empty),
+            provider.flash_loan(token, loan_amount, callback_data)
+        ).await;
+
+        match &result {
+            Ok(_) => self.circuit_breaker.record_success(),
+            Err(_) => self.circuit_breaker.record_failure(),
+        }
+        let tx_hash = result?;
+
+        let profit = TokenAmount::new(amount - loan_amount - loan_fee, route.pools[0].decimals).to_f64();
+        self.metrics.record_executed(profit);
+
+        Ok(ExecutionResult::FlashLoanExecuted { tx_hash, loan_amount, loan_fee })
+    }
+
+    fn validate_route(&self, route: &ArbitrageRoute) -> Result<(), ArbitrageError> {
+        if route.pools.is_empty() || route.pools.len() != route.actions.len() {
+            return Err(ArbitrageError::ValidationFailed(format!("malformed route with {} pools", route.pools.len())));
+        }
+        self.check_route_limits(route)?;
+        if let Some(pool) = route.pools.iter().find(|pool| !self.is_valid_address(&pool.address)) {
+            return Err(ArbitrageError::InvalidAddress(pool.address.clone()));
+        }
+        if route.rate <= 1.0 {
+            return Err(ArbitrageError::ValidationFailed(format!("route rate {} is not profitable", route.rate)));
+        }
+
+        Ok(())
+    }
+
+    pub fn check_route_limits(&self, route: &ArbitrageRoute) -> Result<(), ArbitrageError> {
+        // Deep or multi-bridge routes rarely fill as quoted, so they're capped by max_hops
+        // and max_bridges
+        if route.hop_count() > self.max_hops {
+            return Err(ArbitrageError::ValidationFailed(format!(
+                "route has {} hops, limit is {}",
+                route.hop_count(), self.max_hops
+            )));
+        }
+        if route.bridge_count() > self.max_bridges {
+            return Err(ArbitrageError::ValidationFailed(format!(
+                "route crosses {} bridges, limit is {}",
+                route.bridge_count(), self.max_bridges
+            )));
+        }
+
+        Ok(())
+    }
+
+    fn route_amount(&self, route: &ArbitrageRoute) -> U256 {
+        // Same sizing as calculate_optimal_amount_for_pools, the route's edge over 1.0 standing
+        // in for the price difference and the shallowest pool bounding the trade
+        let min_liquidity = route.pools.iter()
+            .map(|pool| normalize_amount(pool.liquidity, pool.decimals) * depth_factor(pool.kind))
+            .fold(f64::INFINITY, f64::min);
+        let price_multiplier = ((route.rate - 1.0) * 10.0).clamp(self.price_multiplier_min, self.price_multiplier_max);
+        let amount = (min_liquidity * self.liquidity_fraction * price_multiplier).min(min_liquidity * self.max_trade_fraction);
+
+        denormalize_amount(amount, route.pools[0].decimals)
+    }
+
+    async fn execute_hops(&self, route: &ArbitrageRoute, amount_in: U256) -> Result<ExecutionResult, ArbitrageError> {
+        let mut deadline = unix_now() + self.deadline_buffer.as_secs();
+        let mut amount = amount_in;
+        let mut tx_hashes = Vec::with_capacity(route.pools.len());
+        let mut bridge_tx_hashes = Vec::new();
+
+        for (hop, pool) in route.pools.iter().enumerate() {
+            // Bridging is only needed when consecutive hops are on different networks
+            if hop > 0 && route.pools[hop - 1].network != pool.network {
+                let previous = &route.pools[hop - 1].network;
+                let token = route.token_in(hop).parse::<Address>()
+                    .map_err(|_| ArbitrageError::InvalidAddress(route.token_in(hop).to_string()))?;
+
+                let quote = self.quote_bridge(previous, &pool.network, token, amount).await?;
+                deadline += quote.eta.as_secs();
+                let bridge_tx_hash = self.bridge_tokens(previous, &pool.network, token, amount).await?;
+                let timeout = self.bridge_timeout_for(previous, &pool.network);
+                let bridge_route = format!("{}->{}", previous, pool.network);
+                if let Err(err) = self.wait_for_confirmation(&bridge_tx_hash, previous, &bridge_route, timeout).await {
+                    self.metrics.record_bridge_timeout();
+                    return Err(err);
+                }
+                bridge_tx_hashes.push(bridge_tx_hash);
+                amount = quote.amount_out;
+            }
+
+            let request = self.build_swap_request(pool, amount, route.actions[hop], deadline, self.max_slippage);
+            let tx_hash = traced_stage(info_span!("route_hop", hop, tx_hash = field::Empty, duration_ms = field::Empty),
+                self.submit_swap(&request)
+            ).await?;
+
+            tx_hashes.push(tx_hash);
+            amount = request.amount_out_min;
+        }
+
+        let first = &route.pools[0];
+        let amount_in = TokenAmount::new(amount_in, first.decimals).to_f64();
+        self.metrics.record_executed(amount_in * (route.rate - 1.0));
+
+        Ok(ExecutionResult::RouteExecuted {
+            tx_hashes,
+            bridge_tx_hashes,
+        })
+    }
+
+    async fn plan_route_dry_run(&self, route: &ArbitrageRoute, amount_in: U256) -> Result<ExecutionResult, ArbitrageError> {
+        let mut amount = amount_in;
+        let mut estimated_gas = U256::zero();
+        let mut planned_trades = Vec::with_capacity(route.pools.len());
+
+        for (pool, action) in route.pools.iter().zip(&route.actions) {
+            estimated_gas += self.estimate_gas_cost(&pool.network, &self.leg_transaction(pool, U256::zero()), Urgency::Normal).await?;
+            tracing::info!(network = %pool.network, pool = %pool.address, %amount, ?action, "dry run: would execute route hop");
+
+            planned_trades.push(PlannedTrade {
+                network: pool.network.clone(),
+                pool: pool.address.clone(),
+                amount,
+                action: *action,
+            });
+            amount = executor::quote_amount_out(amount, pool.price, *action);
+        }
+
+        Ok(ExecutionResult::DryRun {
+            planned_trades,
+            estimated_gas,
+        })
+    }
+
+    async fn plan_dry_run(&self, opportunity: &ArbitrageOpportunity) -> Result<ExecutionResult, ArbitrageError> {
+        let (source_leg, bridge_leg, target_leg) = self.estimate_route_gas(opportunity, U256::zero(), U256::zero()).await?;
+        let amount = opportunity.required_amount_raw();
+
+        // The target leg sells what the source leg would buy, less whatever the bridge keeps
+        let bought = executor::quote_amount_out(amount, opportunity.source_pool.price, opportunity.direction.source_action());
+        let delivered = if opportunity.needs_bridge() {
+            let token = opportunity.bridged_token().parse::<Address>()
+                .map_err(|_| ArbitrageError::InvalidAddress(opportunity.bridged_token().to_string()))?;
+            let quote = self.quote_bridge(&opportunity.source_pool.network, &opportunity.target_pool.network, token, bought).await?;
+            tracing::info!(
+                source = %opportunity.source_pool.network,
+                target = %opportunity.target_pool.network,
+                sent = %bought,
+                amount_out = %quote.amount_out,
+                "dry run: would bridge tokens"
+            );
+            quote.amount_out
+        } else {
+            bought
+        };
+
+        let planned_trades = vec![
+            PlannedTrade {
+                network: opportunity.source_pool.network.clone(),
+                pool: opportunity.source_pool.address.clone(),
+                amount,
+                action: opportunity.direction.source_action(),
+            },
+            PlannedTrade {
+                network: opportunity.target_pool.network.clone(),
+                pool: opportunity.target_pool.address.clone(),
+                amount: delivered,
+                action: opportunity.direction.target_action(),
+            },
+        ];
+
+        for trade in &planned_trades {
+            tracing::info!(network = %trade.network, pool = %trade.pool, amount = %trade.amount, action = ?trade.action, "dry run: would execute trade");
+        }
+
+        Ok(ExecutionResult::DryRun {
+            planned_trades,
+            estimated_gas: source_leg + bridge_leg + target_leg,
+        })
+    }
+
+    async fn execute_trade(&self, pool: &PoolInfo, amount: U256, action: SwapAction, deadline: u64, max_slippage: f64) -> Result<TradeFill, ArbitrageError> {
+        // Trade against the pool's current state, shrinking the amount to what still fits the
+        // opportunity's slippage tolerance
+        let pool = self.current_pool_state(pool).await;
+        let filled = self.fill_amount(&pool, amount, action, max_slippage)?;
+        if filled < amount {
+            tracing::warn!(pool = %pool.address, requested = %amount, %filled, "liquidity shrank, partially filling trade");
+        }
+
+        let mut request = self.build_swap_request(&pool, filled, action, deadline, max_slippage);
+        let mut signed = None;
+        if let Some(signer) = &self.signer {
+            // The router pulls the input token, so it needs an allowance before the swap lands.
+            // A fresh approval has to be mined first for the simulation to see it.
+            let [token_in, _] = swap_path(&pool, action)?;
+            let router = self.router_for(&pool)?;
+            if let Some(approval) = self.ensure_allowance(&pool.network, token_in, router, filled).await? {
+                self.wait_for_confirmation(&approval, &pool.network, &pool.network, self.deadline_buffer).await?;
+            }
+
+            // Simulate before a nonce is taken, so a doomed swap costs neither gas nor a nonce
+            let mut tx = self.swap_transaction(signer.address(), &pool, &request).await?;
+            let amount_out = self.simulate_swap(&pool.network, &tx).await?;
+            tracing::debug!(pool = %pool.address, %amount_out, "swap simulation passed");
+
+            self.rate_limiter.acquire(&pool.network).await;
+            tx.nonce = self.with_rpc_timeout(&pool.network, self.nonce_manager.next_nonce(&pool.network, signer.address())).await??;
+            let chain_id = self.network(&pool.network)?.chain_id;
+            request.signed_transaction = Some(RawTransaction(signer.sign(&tx, chain_id).await?));
+            signed = Some(tx);
+        }
+        let tx_hash = self.submit_swap(&request).await?;
+        // A replacement goes out publicly, so only public swaps are ever sped up
+        if let Some(tx) = signed.filter(|_| self.submission_mode == SubmissionMode::Public) {
+            self.replaceable.lock().unwrap().insert(tx_hash.clone(), (pool.network.clone(), tx));
+        }
+
+        Ok(TradeFill { tx_hash, filled })
+    }
+
+    pub async fn ensure_allowance(&self, network: &str, token: Address, spender: Address, amount: U256) -> Result<Option<String>, ArbitrageError> {
+        // Approves only when the current allowance falls short, returning the approval's hash.
+        // With approve_max the approval is for U256::MAX, so it happens once per token and spender.
+        let Some(signer) = &self.signer else {
+            // Executors that sign for themselves manage their own approvals
+            return Ok(None);
+        };
+        let config = self.network(network)?;
+        let owner = signer.address();
+
+        self.rate_limiter.acquire(network).await;
+        let current = self.with_rpc_timeout(network, allowance::read_allowance(&config.rpc_url, token, owner, spender)).await??;
+        if current >= amount {
+            return Ok(None);
+        }
+
+        // With use_permits a signed permit rides along with the swap instead, when the token
+        // and signer support one
+        let approval = if self.approve_max { U256::MAX } else { amount };
+        if self.use_permits {
+            let deadline = unix_now() + self.deadline_buffer.as_secs();
+            let signed = async {
+                let permit = self.build_permit(network, token, spender, approval, deadline).await?;
+                let signature = signer.sign_digest(permit.digest()).await?;
+                Ok::<_, ArbitrageError>(permit::SignedPermit { permit, signature })
+            };
+            match signed.await {
+                Ok(signed) => {
+                    tracing::info!(%network, token = ?token, spender = ?spender, %current, "signed token permit");
+                    self.permits.lock().unwrap().insert((network.to_string(), token, spender), signed);
+                    return Ok(None);
+                }
+                Err(err) => tracing::debug!(%network, token = ?token, error = %err, "no permit, approving on chain"),
+            }
+        }
+
+        // The swap's later nonce keeps it queued behind the approval, so no need to wait here
+        let calldata = allowance::approve_calldata(spender, approval);
+        let tx_hash = self.send_signed_call(signer.as_ref(), network, token, U256::zero(), calldata, APPROVE_GAS_LIMIT).await?;
+        tracing::info!(%network, token = ?token, spender = ?spender, %current, %tx_hash, "submitted token approval");
+
+        Ok(Some(tx_hash))
+    }
+
+    pub async fn build_permit(&self,
+        network: &str,
+        token: Address,
+        spender: Address,
+        amount: U256,
+        deadline: u64
+    ) -> Result<PermitData, ArbitrageError> {
+        // The permit the signer would sign to let spender move amount of token until deadline,
+        // with the token's current nonce and domain separator. Fails for tokens without EIP-2612.
+        let owner = self.signer.as_ref()
+            .ok_or_else(|| ArbitrageError::InvalidConfig("building a permit needs a signer".to_string()))?
+            .address();
+        let config = self.network(network)?;
+
+        self.rate_limiter.acquire(network).await;
+        let (nonce, domain_separator) = self.with_rpc_timeout(network, permit::read_permit_domain(&config.rpc_url, token, owner)).await??;
+
+        Ok(PermitData { token, owner, spender, value: amount, nonce, deadline, domain_separator })
+    }
+
+    pub async fn wrap_native(&self, network: &str, amount: U256) -> Result<String, ArbitrageError> {
+        // deposit() on the network's wrapped native contract, sending amount of the native coin
+        let (signer, wrapped) = self.wrapping(network)?;
+        let tx_hash = self.send_signed_call(signer, network, wrapped, amount, allowance::deposit_calldata(), WRAP_GAS_LIMIT).await?;
+        tracing::info!(%network, %amount, %tx_hash, "submitted native wrap");
+
+        Ok(tx_hash)
+    }
+
+    pub async fn unwrap_native(&self, network: &str, amount: U256) -> Result<String, ArbitrageError> {
+        // withdraw(amount) on the network's wrapped native contract
+        let (signer, wrapped) = self.wrapping(network)?;
+        let tx_hash = self.send_signed_call(signer, network, wrapped, U256::zero(), allowance::withdraw_calldata(amount), WRAP_GAS_LIMIT).await?;
+        tracing::info!(%network, %amount, %tx_hash, "submitted native unwrap");
+
+        Ok(tx_hash)
+    }
+
+    fn wrapping(&self, network: &str) -> Result<(&dyn Signer, Address), ArbitrageError> {
+        // Wrapping moves the wallet's own funds, so it is only possible with a signer
+        let signer = self.signer.as_deref()
+            .ok_or_else(|| ArbitrageError::InvalidConfig("wrapping native tokens needs a signer".to_string()))?;
+        let wrapped = self.network(network)?.wrapped_native
+            .ok_or_else(|| ArbitrageError::InvalidConfig(format!("no wrapped native token configured for {}", network)))?;
+
+        Ok((signer, wrapped))
+    }
+
+    async fn send_signed_call(&self,
+        signer: &dyn Signer,
+        network: &str,
+        to: Address,
+        value: U256,
+        calldata: Vec<u8>,
+        gas_limit: u64
+    ) -> Result<String, ArbitrageError> {
+        // Contract call from the signer's account at current fees, broadcast without waiting
+        let gas = self.suggest_gas_price(network).await?;
+        self.rate_limiter.acquire(network).await;
+        let nonce = self.with_rpc_timeout(network, self.nonce_manager.next_nonce(network, signer.address())).await??;
+        let mut tx = self.secure_transaction_builder_1559(
+            network,
+            &format!("{:?}", to),
+            0,
+            calldata,
+            gas_limit,
+            gas.max_fee_per_gas.low_u64(),
+            gas.max_priority_fee_per_gas.low_u64(),
+            nonce.low_u64()
+        )?;
+        // The builder takes value as u64, too small for whole native amounts
+        tx.value = value;
+        let tx_hash = self.broadcast(signer, network, &tx).await?;
+        self.replaceable.lock().unwrap().insert(tx_hash.clone(), (network.to_string(), tx));
+
+        Ok(tx_hash)
+    }
+
+    async fn broadcast(&self, signer: &dyn Signer, network: &str, tx: &SecureTransaction) -> Result<String, ArbitrageError> {
+        // Sign for the network's chain and send through its RPC endpoint
+        let raw = signer.sign(tx, self.network(network)?.chain_id).await?;
+        self.submit_raw(network, raw).await
+    }
+
+    pub fn encode_unsigned(&self, tx: &SecureTransaction, chain_id: u64) -> Result<UnsignedTransaction, ArbitrageError> {
+        // For signing outside the bot: a multisig or remote signer signs tx_hash_to_sign() and
+        // the signed bytes go back through send_raw_transaction
+        encode_unsigned_transaction(tx, chain_id)
+    }
+
+    pub async fn send_raw_transaction(&self, network: &str, signed: Vec<u8>) -> Result<String, ArbitrageError> {
+        // eth_sendRawTransaction on the network, returning the transaction hash. Bytes that
+        // aren't a signed legacy or EIP-1559 transaction for the network's chain never leave.
+        let config = self.network(network)?;
+        let (tx_type, chain_id) = signed_transaction_chain_id(&signed)?;
+        if chain_id != config.chain_id {
+            return Err(ArbitrageError::ValidationFailed(format!(
+                "type {} transaction is signed for chain {}, {} is chain {}",
+                tx_type, chain_id, network, config.chain_id
+            )));
+        }
+
+        self.submit_raw(network, signed).await
+    }
+
+    async fn submit_raw(&self, network: &str, raw: Vec<u8>) -> Result<String, ArbitrageError> {
+        // Unchecked, for bytes the configured signer produced
+        let config = self.network(network)?;
+        self.rate_limiter.acquire(network).await;
+        let transport = web3::transports::Http::new(&config.rpc_url)?;
+        let tx_hash = self.with_rpc_timeout(network, web3::Web3::new(transport).eth().send_raw_transaction(web3::types::Bytes(raw))).await??;
+
+        Ok(format!("{:?}", tx_hash))
+    }
+
+    pub async fn speed_up(&self, network: &str, original: &SecureTransaction, new_gas_price: U256) -> Result<String, ArbitrageError> {
+        // Resubmit the original's nonce at a higher price so miners take it in place of the
+        // stuck transaction. Nodes drop replacements that don't outbid by a margin, 12.5% here.
+        let signer = self.signer.as_ref()
+            .ok_or_else(|| ArbitrageError::InvalidConfig("replacing a transaction needs a signer".to_string()))?;
+        if new_gas_price < replacement_floor(original.gas_price) {
+            return Err(ArbitrageError::ReplacementUnderpriced { original: original.gas_price, replacement: new_gas_price });
+        }
+        if new_gas_price > U256::from(MAX_GAS_PRICE_WEI) {
+            return Err(ArbitrageError::GasPriceTooHigh(new_gas_price.min(U256::from(u64::MAX)).as_u64()));
+        }
+
+        let mut tx = original.clone();
+        tx.gas_price = new_gas_price;
+        if tx.tx_type == 2 {
+            // The tip has to clear the same margin as the max fee
+            tx.max_fee_per_gas = Some(new_gas_price);
+            tx.max_priority_fee_per_gas = original.max_priority_fee_per_gas
+                .map(|tip| replacement_floor(tip).min(new_gas_price));
+        }
+
+        let tx_hash = self.broadcast(signer.as_ref(), network, &tx).await?;
+        tracing::info!(%network, nonce = %tx.nonce, gas_price = %new_gas_price, %tx_hash, "submitted replacement transaction");
+        self.replaceable.lock().unwrap().insert(tx_hash.clone(), (network.to_string(), tx));
+
+        Ok(tx_hash)
+    }
+
+    pub async fn cancel_pending(&self, network: &str, nonce: U256, gas_price: U256) -> Result<String, ArbitrageError> {
+        // A zero-value transfer to the signer's own address at the stuck nonce. Once it lands
+        // the original can never be mined. When the original is one of ours it must be
+        // outbid like any replacement, and it is no longer sped up.
+        let signer = self.signer.as_ref()
+            .ok_or_else(|| ArbitrageError::InvalidConfig("cancelling a transaction needs a signer".to_string()))?;
+        let original = self.replaceable.lock().unwrap().values()
+            .find(|(pending_network, tx)| pending_network == network && tx.nonce == nonce)
+            .map(|(_, tx)| tx.gas_price);
+        if let Some(original) = original {
+            if gas_price < replacement_floor(original) {
+                return Err(ArbitrageError::ReplacementUnderpriced { original, replacement: gas_price });
+            }
+        }
+
+        let gas_price = u64::try_from(gas_price).unwrap_or(u64::MAX);
+        let tx = self.secure_transaction_builder_1559(
+            network,
+            &format!("{:?}", signer.address()),
+            0,
+            Vec::new(),
+            21_000,
+            gas_price,
+            gas_price,
+            nonce.low_u64()
+        )?;
+        let tx_hash = self.broadcast(signer.as_ref(), network, &tx).await?;
+        tracing::info!(%network, %nonce, %gas_price, %tx_hash, "submitted cancellation transaction");
+        self.replaceable.lock().unwrap()
+            .retain(|_, (pending_network, pending)| !(pending_network == network && pending.nonce == nonce));
+
+        Ok(tx_hash)
+    }
+
+    async fn swap_transaction(&self, from: Address, pool: &PoolInfo, request: &SwapRequest) -> Result<SecureTransaction, ArbitrageError> {
+        // Router call from the signer's account at the network's current fees, carrying the
+        // permit ensure_allowance signed for it if any. The nonce is left at zero for the
+        // caller to fill in; the swap is signed once so retries rebroadcast the same nonce
+        // rather than queueing a second swap.
+        let [token_in, _] = swap_path(pool, request.action)?;
+        let router = request.router.parse::<Address>()
+            .map_err(|_| ArbitrageError::InvalidAddress(request.router.clone()))?;
+        let signed = self.permits.lock().unwrap().remove(&(pool.network.clone(), token_in, router));
+
+        self.swap_call(from, pool, request, signed).await
+    }
+
+    async fn swap_call(&self,
+        from: Address,
+        pool: &PoolInfo,
+        request: &SwapRequest,
+        signed: Option<permit::SignedPermit>
+    ) -> Result<SecureTransaction, ArbitrageError> {
+        let config = self.network(&pool.network)?;
+        let path = swap_path(pool, request.action)?;
+
+        // A permit goes first in a multicall, costing roughly what the approve's execution
+        // would have
+        let mut calldata = request.encode_router_calldata(&path, from);
+        let mut gas_limit = config.base_gas;
+        if let Some(signed) = signed {
+            calldata = permit::multicall_calldata(vec![permit::self_permit_calldata(&signed), calldata]);
+            gas_limit += APPROVE_GAS_LIMIT;
+        }
+
+        let gas = self.suggest_gas_price(&pool.network).await?;
+        self.secure_transaction_builder_1559(
+            &pool.network,
+            &request.router,
+            0,
+            calldata,
+            gas_limit,
+            gas.max_fee_per_gas.low_u64(),
+            gas.max_priority_fee_per_gas.low_u64(),
+            0
+        )
+    }
+
+    pub async fn simulate_arbitrage(&self, opportunity: &ArbitrageOpportunity) -> Result<SimulationResult, ArbitrageError> {
+        // eth_call both swaps against current chain state, with the bridge quote in between,
+        // and price the outcome without sending anything. Calls come from the signer's account,
+        // so the source swap needs its balance and allowance there, and the target swap needs
+        // inventory on the target network covering the bridged amount. Pool fees are already
+        // taken out of the simulated outputs, so the cost model is given fee-free pools.
+        let from = self.signer.as_ref().map(|signer| signer.address()).unwrap_or_default();
+        let deadline = unix_now() + self.deadline_buffer.as_secs();
+        let max_slippage = self.max_slippage_for(opportunity);
+
+        let source_pool = self.current_pool_state(&opportunity.source_pool).await;
+        let source_amount_in = opportunity.required_amount_raw();
+        let source_request = self.build_swap_request(&source_pool, source_amount_in, opportunity.direction.source_action(), deadline, max_slippage);
+        let source_tx = self.swap_call(from, &source_pool, &source_request, None).await?;
+        let source_amount_out = self.simulate_swap(&source_pool.network, &source_tx).await?;
+
+        let bridge_quote = match opportunity.needs_bridge() {
+            true => {
+                let token = opportunity.bridged_token().parse::<Address>()
+                    .map_err(|_| ArbitrageError::InvalidAddress(opportunity.bridged_token().to_string()))?;
+                Some(self.quote_bridge(&source_pool.network, &opportunity.target_pool.network, token, source_amount_out).await?)
+            }
+            false => None,
+        };
+        let target_amount_in = bridge_quote.map_or(source_amount_out, |quote| quote.amount_out);
+        let target_deadline = deadline + bridge_quote.map_or(0, |quote| quote.eta.as_secs());
+
+        let target_pool = self.current_pool_state(&opportunity.target_pool).await;
+        let target_request = self.build_swap_request(&target_pool, target_amount_in, opportunity.direction.target_action(), target_deadline, max_slippage);
+        let target_tx = self.swap_call(from, &target_pool, &target_request, None).await?;
+        let target_amount_out = self.simulate_swap(&target_pool.network, &target_tx).await?;
+
+        let gross_profit = TokenAmount::new(target_amount_out, target_pool.decimals).to_f64()
+            - TokenAmount::new(source_amount_in, source_pool.decimals).to_f64();
+        let mut fee_free = opportunity.clone();
+        fee_free.source_pool.fee_tier = 0;
+        fee_free.target_pool.fee_tier = 0;
+        let execution_cost = self.estimate_execution_cost(&fee_free).await?;
+
+        Ok(SimulationResult {
+            source_amount_in,
+            source_amount_out,
+            bridge_quote,
+            target_amount_in,
+            target_amount_out,
+            gross_profit,
+            execution_cost,
+            net_profit: gross_profit - execution_cost,
+        })
+    }
+
+    pub async fn simulate_swap(&self, network: &str, tx: &SecureTransaction) -> Result<U256, ArbitrageError> {
+        // eth_call of the transaction against the latest block, from the signer's account when
+        // there is one. Returns the router's final output amount, or the revert reason.
+        let config = self.network(network)?;
+        let call = web3::types::CallRequest {
+            from: self.signer.as_ref().map(|signer| signer.address()),
+            to: Some(tx.to),
+            gas: Some(tx.gas_limit),
+            value: Some(tx.value),
+            data: Some(web3::types::Bytes(tx.data.clone())),
+            ..Default::default()
+        };
+
+        self.rate_limiter.acquire(network).await;
+        let transport = web3::transports::Http::new(&config.rpc_url)?;
+        let output = match self.with_rpc_timeout(network, web3::Web3::new(transport).eth().call(call, None)).await? {
+            Ok(output) => output.0,
+            Err(web3::Error::Rpc(err)) => {
+                let data = err.data.as_ref().and_then(|data| data.as_str()).unwrap_or_default();
+                let reason = decode_revert_reason(data).unwrap_or(err.message);
+                tracing::warn!(%network, to = ?tx.to, %reason, "swap simulation reverted");
+                return Err(ArbitrageError::SimulationReverted { reason });
+            }
+            Err(err) => return Err(err.into()),
+        };
+        let output = permit::last_call_output(&tx.data, output);
+
+        // swapExactTokensForTokens returns uint256[] amounts, the last being the output
+        let amounts = ethabi::decode(&[ethabi::ParamType::Array(Box::new(ethabi::ParamType::Uint(256)))], &output)
+            .ok()
+            .and_then(|mut tokens| tokens.pop())
+            .and_then(Token::into_array)
+            .and_then(|amounts| amounts.into_iter().last())
+            .and_then(Token::into_uint);
+        match amounts {
+            Some(amount_out) => Ok(amount_out),
+            None if output.len() >= 32 => Ok(U256::from_big_endian(&output[output.len() - 32..])),
+            None => Err(ArbitrageError::SimulationReverted { reason: format!("unexpected {}-byte output", output.len()) }),
+        }
+    }
+
+    async fn current_pool_state(&self, pool: &PoolInfo) -> PoolInfo {
+        // Without a fetcher the opportunity's snapshot is the best information available
+        if self.pool_fetcher.is_none() {
+            return pool.clone();
+        }
+
+        match self.pool_state(&pool.network, &pool.address).await {
+            Ok(current) => current,
+            Err(err) => {
+                tracing::warn!(pool = %pool.address, error = %err, "using stale pool state");
+                pool.clone()
+            }
+        }
+    }
+
+    pub fn fill_amount(&self, pool: &PoolInfo, amount: U256, action: SwapAction, max_slippage: f64) -> Result<U256, ArbitrageError> {
+        // Largest amount, up to the requested one, whose price impact stays within max_slippage
+        let requested = TokenAmount::new(amount, pool.decimals).to_f64();
+        let impact = self.pool_price_impact(pool, requested, action);
+        if impact <= max_slippage {
+            return Ok(amount);
+        }
+
+        let (reserve_in, reserve_out) = match action {
+            SwapAction::Sell => (pool.reserve0, pool.reserve1),
+            SwapAction::Buy => (pool.reserve1, pool.reserve0),
+        };
+        let max_amount = match (pool.kind, reserve_in, reserve_out) {
+            (PoolKind::ConstantProduct, Some(reserve_in), Some(_)) => {
+                let reserve_in = normalize_amount(reserve_in, pool.decimals);
+                let fee = (FEE_TIER_DENOMINATOR - pool.fee_tier as f64) / FEE_TIER_DENOMINATOR;
+
+                // Inverse of calculate_price_impact_v2: impact == max_slippage at this size
+                reserve_in * (1.0 / (1.0 - max_slippage) - 1.0 / fee)
+            }
+            (PoolKind::ConstantProduct, _, _) => {
+                let liquidity = normalize_amount(pool.liquidity, pool.decimals);
+                liquidity * max_slippage / (1.0 - max_slippage)
+            }
+            // No closed-form inverse for the other curves; impact grows with size, so bisect
+            _ => {
+                let (mut low, mut high) = (0.0, requested);
+                for _ in 0..FILL_SEARCH_ITERATIONS {
+                    let mid = (low + high) / 2.0;
+                    if self.pool_price_impact(pool, mid, action) <= max_slippage {
+                        low = mid;
+                    } else {
+                        high = mid;
+                    }
+                }
+                low
+            }
+        };
+
+        if max_amount.is_nan() || max_amount <= 0.0 {
+            return Err(ArbitrageError::SlippageExceeded { limit: max_slippage, actual: impact });
+        }
+
+        Ok(denormalize_amount(max_amount, pool.decimals).min(amount))
+    }
+
+    async fn submit_swap(&self, request: &SwapRequest) -> Result<String, ArbitrageError> {
+        let span = info_span!("submit_swap",
+            pool = %request.pool,
+            submission = %self.submission_mode,
+            relay_fallback = field::Empty
+        );
+
+        self.retry_with_backoff(|| async {
+            // Checked per attempt so retries cannot push a stale swap through either
+            let now = unix_now();
+            if now > request.deadline {
+                return Err(ArbitrageError::DeadlineExceeded { deadline: request.deadline, now });
+            }
+
+            let network = &request.network;
+            match &self.submission_mode {
+                SubmissionMode::Public => self.with_rpc_timeout(network, self.executor.swap(request)).await?,
+                SubmissionMode::PrivateRelay { endpoint } => match self.with_rpc_timeout(network, self.executor.swap_private(request, endpoint)).await? {
+                    Err(ArbitrageError::RelayRejected(reason)) => {
+                        tracing::warn!(endpoint = %redact_url(endpoint), %reason, "private relay rejected swap, submitting publicly");
+                        tracing::Span::current().record("relay_fallback", true);
+                        self.with_rpc_timeout(network, self.executor.swap(request)).await?
+                    }
+                    result => result,
+                },
+            }
+        }, self.max_retries).instrument(span).await
+    }
+
+    pub fn build_swap_request(&self, pool: &PoolInfo, amount_in: U256, action: SwapAction, deadline: u64, max_slippage: f64) -> SwapRequest {
+        // Minimum output is the quoted output less the slippage tolerance
+        let expected_amount_out = executor::quote_amount_out(amount_in, pool.price, action);
+        let tolerance_ppm = ((1.0 - max_slippage).clamp(0.0, 1.0) * 1_000_000.0) as u64;
+        let amount_out_min = expected_amount_out.full_mul(U256::from(tolerance_ppm)) / U512::from(1_000_000);
+
+        SwapRequest {
+            network: pool.network.clone(),
+            pool: pool.address.clone(),
+            router: self.router_for(pool).map(|router| format!("{:?}", router)).unwrap_or_else(|_| pool.address.clone()),
+            action,
+            amount_in,
+            expected_amount_out,
+            amount_out_min: U256::try_from(amount_out_min).unwrap_or(expected_amount_out),
+            deadline,
+            signed_transaction: None,
+        }
+    }
+
+    async fn bridge_tokens(&self, source: &str, target: &str, token: Address, amount: U256) -> Result<String, ArbitrageError> {
+        self.retry_with_backoff(|| async {
+            self.with_rpc_timeout(source, self.bridge.bridge(source, target, token, amount)).await?
+        }, self.max_retries).await
+    }
+
+    pub async fn quote_bridge(&self, source: &str, target: &str, token: Address, amount: U256) -> Result<BridgeQuote, ArbitrageError> {
+        // What a transfer of amount from source to target would deliver
+        self.with_rpc_timeout(source, self.bridge.quote_bridge(source, target, token, amount)).await?
+    }
+
+    async fn bridge_cost(&self, opportunity: &ArbitrageOpportunity) -> Result<f64, ArbitrageError> {
+        // What the bridge keeps, in the profit token. The quote is on the source leg's expected
+        // output, and the round trip comes back short by the same share of the required amount.
+        let token = opportunity.bridged_token().parse::<Address>()
+            .map_err(|_| ArbitrageError::InvalidAddress(opportunity.bridged_token().to_string()))?;
+        let sent = executor::quote_amount_out(
+            opportunity.required_amount_raw(),
+            opportunity.source_pool.price,
+            opportunity.direction.source_action()
+        );
+        if sent.is_zero() {
+            return Ok(0.0);
+        }
+
+        let quote = self.quote_bridge(&opportunity.source_pool.network, &opportunity.target_pool.network, token, sent).await?;
+        let kept = sent.saturating_sub(quote.amount_out);
+        Ok(opportunity.required_amount * u256_to_f64(kept) / u256_to_f64(sent))
+    }
+
+    fn observe(&self, notify: impl FnOnce(&dyn ExecutionObserver)) {
+        if let Some(observer) = &self.observer {
+            notify(observer.as_ref());
+        }
+    }
+
+    async fn with_rpc_timeout<F: Future>(&self, network: &str, call: F) -> Result<F::Output, ArbitrageError> {
+        // One network call bounded by rpc_timeout, so a hung connection fails instead of
+        // blocking the arbitrage indefinitely
+        tokio::time::timeout(self.rpc_timeout, call).await
+            .map_err(|_| ArbitrageError::RpcTimeout { network: network.to_string() })
+    }
+
+    pub async fn retry_with_backoff<F, Fut, T>(&self, mut op: F, max_retries: u32) -> Result<T, ArbitrageError>
+    where
+        F: FnMut() -> Fut,
+        Fut: Future<Output = Result<T, ArbitrageError>>,
+    {
+        // Retry transient failures with exponential backoff plus jitter; permanent errors return at once.
+        // Inside an execution each retry also spends from its retry budget, and once that is out
+        // the error is returned as is.
+        let mut attempt = 0;
+
+        loop {
+            match op().await {
+                Ok(value) => return Ok(value),
+                Err(err) if err.is_transient() && attempt < max_retries && spend_execution_retry() => {
+                    let backoff = self.retry_base_delay.saturating_mul(2u32.saturating_pow(attempt));
+                    let jitter = self.rng.lock().unwrap().gen_range(0.0..1.0);
+                    sleep(backoff + self.retry_base_delay.mul_f64(jitter)).await;
+                    attempt += 1;
+                }
+                Err(err) => return Err(err),
+            }
+        }
+    }
+
+    pub fn bridge_timeout_for(&self, source: &str, target: &str) -> Duration {
+        // Per-route finality differs widely, e.g. L2-to-L2 versus routes through L1
+        self.bridge_timeouts.get(&(source.to_string(), target.to_string()))
+            .copied()
+            .unwrap_or(self.default_bridge_timeout)
+    }
+
+    async fn wait_for_confirmation(&self, tx_hash: &str, network: &str, route: &str, timeout: Duration) -> Result<U256, ArbitrageError> {
+        // Poll the receipt until it has enough blocks on top, or the timeout passes.
+        // Returns the gas paid in the network's native wei. With speed_up_after set, a locally
+        // signed transaction that stays pending that long is replaced at a higher price, and
+        // whichever of the original and its replacements lands is the one confirmed. A receipt
+        // poll that hangs past rpc_timeout fails with RpcTimeout rather than waiting out timeout.
+        let rpc_url = &self.network(network)?.rpc_url;
+        let parse = |tx_hash: &str| tx_hash.parse::<web3::types::H256>()
+            .map_err(|_| ArbitrageError::ValidationFailed(format!("invalid transaction hash {}", tx_hash)));
+        let mut pending = vec![(tx_hash.to_string(), parse(tx_hash)?)];
+        let start = std::time::Instant::now();
+        let mut submitted_at = start;
+
+        let outcome = loop {
+            if start.elapsed() >= timeout {
+                break Err(ArbitrageError::ConfirmationTimeout {
+                    tx_hash: tx_hash.to_string(),
+                    route: route.to_string(),
+                    seconds: timeout.as_secs(),
+                });
+            }
+
+            let mut settled = None;
+            for (mined_hash, hash) in &pending {
+                self.rate_limiter.acquire(network).await;
+                match self.with_rpc_timeout(network, self.confirmer.receipt_status(network, rpc_url, *hash)).await.and_then(|status| status) {
+                    Ok(ReceiptStatus::Mined { confirmations, gas_paid }) if confirmations >= self.min_confirmations_for(network) => {
+                        tracing::info!(tx_hash = %mined_hash, confirmations, %gas_paid, "transaction confirmed");
+                        settled = Some(Ok(gas_paid));
+                    }
+                    Ok(ReceiptStatus::Reverted { gas_used }) => {
+                        tracing::warn!(tx_hash = %mined_hash, %network, %gas_used, "transaction reverted");
+                        settled = Some(Err(ArbitrageError::TransactionReverted {
+                            tx_hash: mined_hash.clone(),
+                            network: network.to_string(),
+                            gas_used,
+                        }));
+                    }
+                    Ok(_) => {}
+                    Err(err) => settled = Some(Err(err)),
+                }
+                if settled.is_some() {
+                    break;
+                }
+            }
+            if let Some(outcome) = settled {
+                break outcome;
+            }
+
+            if let Some(stall) = self.speed_up_after {
+                let (latest, _) = pending.last().cloned().unwrap_or_default();
+                let stuck = self.replaceable.lock().unwrap().get(&latest).map(|(_, tx)| tx.clone());
+                if let Some(stuck) = stuck.filter(|_| submitted_at.elapsed() >= stall) {
+                    match self.speed_up(network, &stuck, replacement_floor(stuck.gas_price)).await {
+                        Ok(replacement) => {
+                            tracing::warn!(%tx_hash, %replacement, stalled_secs = submitted_at.elapsed().as_secs(), "transaction stalled, replaced at a higher gas price");
+                            pending.push((replacement.clone(), parse(&replacement)?));
+                        }
+                        Err(err) => tracing::warn!(%tx_hash, error = %err, "cannot speed up stalled transaction"),
+                    }
+                    submitted_at = std::time::Instant::now();
+                }
+            }
+
+            sleep(self.confirmation_poll_interval).await;
+        };
+
+        let mut replaceable = self.replaceable.lock().unwrap();
+        for (pending_hash, _) in &pending {
+            replaceable.remove(pending_hash);
+        }
+        outcome
+    }
+
+    fn is_valid_address(&self, address: &str) -> bool {
+        // Validate Ethereum address format
+        if !address.starts_with("0x") || address.len() != 42 {
+            return false;
+        }
+        
+        // Check if all characters after 0x are hex
+        address[2..].chars().all(|c| c.is_ascii_hexdigit())
+    }
+
+    pub fn is_valid_checksummed_address(&self, address: &str) -> bool {
+        // Validate format, then the EIP-55 checksum when the address is mixed case
+        if !self.is_valid_address(address) {
+            return false;
+        }
+
+        let hex_part = &address[2..];
+        let has_lower = hex_part.chars().any(|c| c.is_ascii_lowercase());
+        let has_upper = hex_part.chars().any(|c| c.is_ascii_uppercase());
+        if !(has_lower && has_upper) {
+            return true; // single-case addresses carry no checksum
+        }
+
+        let hash = web3::signing::keccak256(hex_part.to_ascii_lowercase().as_bytes());
+        hex_part.chars().enumerate().all(|(i, c)| {
+            if !c.is_ascii_alphabetic() {
+                return true;
+            }
+
+            // Each hex letter is uppercase iff the matching hash nibble is >= 8
+            let nibble = if i % 2 == 0 { hash[i / 2] >> 4 } else { hash[i / 2] & 0x0f };
+            c.is_ascii_uppercase() == (nibble >= 8)
+        })
+    }
+}
+
+struct TradeFill {
+    tx_hash: String,
+    filled: U256,
+}
+
+// Retries left to the execution running on the current task. Legs run concurrently are
+// joined on that task, so they share it.
+struct ExecutionRetries {
+    started: Instant,
+    spent: std::cell::Cell<u32>,
+    max_retries: Option<u32>,
+    window: Option<Duration>,
+}
+
+impl ExecutionRetries {
+    fn new(max_retries: Option<u32>, window: Option<Duration>) -> Self {
+        Self { started: Instant::now(), spent: std::cell::Cell::new(0), max_retries, window }
+    }
+
+    fn try_spend(&self) -> bool {
+        let within_window = self.window.is_none_or(|window| self.started.elapsed() < window);
+        let within_count = self.max_retries.is_none_or(|max| self.spent.get() < max);
+        if !(within_window && within_count) {
+            tracing::warn!(spent = self.spent.get(), elapsed_ms = self.started.elapsed().as_millis() as u64, "retry budget exhausted");
+            return false;
+        }
+
+        self.spent.set(self.spent.get() + 1);
+        true
+    }
+}
+
+tokio::task_local! {
+    static EXECUTION_RETRIES: ExecutionRetries;
+}
+
+fn spend_execution_retry() -> bool {
+    // Outside an execution only max_retries applies
+    EXECUTION_RETRIES.try_with(ExecutionRetries::try_spend).unwrap_or(true)
+}
+
+// Both swaps of an opportunity once mined, with the gas each paid
+struct SettledLegs {
+    source_fill: TradeFill,
+    source_gas: U256,
+    bridge_tx_hash: String,
+    bridge_gas: U256,
+    target_fill: TradeFill,
+    // What the target leg was sized to sell, in the target leg's input token
+    target_requested: U256,
+    target_gas: U256,
+}
+
+fn leg_fill_ratio(filled: U256, requested: U256) -> f64 {
+    if requested.is_zero() { 1.0 } else { u256_to_f64(filled) / u256_to_f64(requested) }
+}
+
+trait StageOutput {
+    fn tx_hash(&self) -> &str;
+}
+
+impl StageOutput for String {
+    fn tx_hash(&self) -> &str {
+        self
+    }
+}
+
+impl StageOutput for TradeFill {
+    fn tx_hash(&self) -> &str {
+        &self.tx_hash
+    }
+}
+
+async fn traced_stage<F, T>(span: tracing::Span, stage: F) -> Result<T, ArbitrageError>
+where
+    F: Future<Output = Result<T, ArbitrageError>>,
+    T: StageOutput,
+{
+    // Run one arbitrage stage inside its span, recording duration and resulting tx hash
+    let started = std::time::Instant::now();
+    let result = stage.instrument(span.clone()).await;
+
+    span.record("duration_ms", started.elapsed().as_millis() as u64);
+    match &result {
+        Ok(output) => {
+            span.record("tx_hash", output.tx_hash());
+        }
+        Err(err) => span.in_scope(|| tracing::warn!(error = %err, "stage failed")),
+    }
+
+    result
+}
+
+fn execution_report(
+    opportunity: &ArbitrageOpportunity,
+    fill_ratio: f64,
+    gas_paid: [U256; 2],
+    balances: Option<((U256, U256), (U256, U256))>,
+    leg_hashes: Vec<String>
+) -> ExecutionReport {
+    // gas_paid is [source network, target network]; balances are (start, end) before and after
+    let [source_gas, target_gas] = gas_paid;
+    let expected_gross = opportunity.profit_potential * fill_ratio;
+    let start_units = |raw: U256| TokenAmount::new(raw, opportunity.source_pool.decimals).to_f64();
+    let end_units = |raw: U256| TokenAmount::new(raw, opportunity.target_pool.decimals).to_f64();
+
+    let realized_gross = match balances {
+        Some(((start_before, end_before), (start_after, end_after))) => {
+            (end_units(end_after) - end_units(end_before)) - (start_units(start_before) - start_units(start_after))
+        }
+        None => expected_gross,
+    };
+    // Actual gas priced with the quote's native prices; the estimated cost otherwise
+    let gas_cost = match &opportunity.gas_quote {
+        Some(quote) => {
+            TokenAmount::new(source_gas, 18).to_f64() * quote.source_native_price
+                + TokenAmount::new(target_gas, 18).to_f64() * quote.target_native_price
+        }
+        None => opportunity.execution_cost * fill_ratio,
+    };
+    let slippage_incurred = if expected_gross > 0.0 { 1.0 - realized_gross / expected_gross } else { 0.0 };
+
+    ExecutionReport {
+        expected_net_profit: opportunity.net_profit,
+        realized_net_profit: realized_gross - gas_cost,
+        total_gas_paid: source_gas.saturating_add(target_gas),
+        slippage_incurred,
+        leg_hashes,
+        from_balances: balances.is_some(),
+    }
+}
+
+fn replacement_floor(price: U256) -> U256 {
+    // Lowest price a replacement may bid: 12.5% above the original, rounded up
+    price.saturating_add((price.saturating_add(U256::from(7))) / 8)
+}
+
+fn decode_revert_reason(data: &str) -> Option<String> {
+    // Error(string) revert data: selector 0x08c379a0 followed by the ABI-encoded message
+    let bytes = hex::decode(data.trim_start_matches("0x")).ok()?;
+    let payload = bytes.strip_prefix(&[0x08, 0xc3, 0x79, 0xa0][..])?;
+
+    ethabi::decode(&[ethabi::ParamType::String], payload).ok()?
+        .pop()?
+        .into_string()
+}
+
+fn check_cancelled(cancel: &CancellationToken) -> Result<(), ArbitrageError> {
+    if cancel.is_cancelled() {
+        return Err(ArbitrageError::Cancelled);
+    }
+    Ok(())
+}
+
+fn swap_path(pool: &PoolInfo, action: SwapAction) -> Result<[Address; 2], ArbitrageError> {
+    // Input token first: selling gives up token0, buying gives up token1
+    let token0 = pool.token0.parse::<Address>().map_err(|_| ArbitrageError::InvalidAddress(pool.token0.clone()))?;
+    let token1 = pool.token1.parse::<Address>().map_err(|_| ArbitrageError::InvalidAddress(pool.token1.clone()))?;
+
+    Ok(match action {
+        SwapAction::Sell => [token0, token1],
+        SwapAction::Buy => [token1, token0],
+    })
+}
+
+fn sandwich_risk_from_impact(price_impact: f64, fee_tier: u32) -> f64 {
+    // price_impact includes the trade's own fee, as calculate_price_impact_v2 reports it.
+    // What remains is the price move an attacker captures, paying the fee on both their
+    // front-run and back-run.
+    let fee = fee_tier as f64 / FEE_TIER_DENOMINATOR;
+    let price_move = price_impact - fee;
+    if price_move <= 0.0 {
+        return 0.0;
+    }
+
+    (price_move / (price_move + 2.0 * fee)).clamp(0.0, 1.0)
+}
+
+fn unix_now() -> u64 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|elapsed| elapsed.as_secs())
+        .unwrap_or_default()
+}
+
+fn u256_to_f64(value: U256) -> f64 {
+    // Lossy conversion for display and ratio math
+    value.to_string().parse::<f64>().unwrap_or(f64::MAX)
+}
+
+// FFI interface for Python integration
+//
+// Every entry point checks its pointers for null and reports FFI_ERR_NULL_POINTER (or a
+// sentinel return value) instead of dereferencing them. A non-null but dangling pointer,
+// such as a core used after free_arbitrage_core, cannot be detected and is undefined behaviour.
+
+// Shared by every FFI call; Python validates in a loop, and a runtime per call is expensive.
+// None if the runtime could not be created.
+static FFI_RUNTIME: std::sync::OnceLock<Option<tokio::runtime::Runtime>> = std::sync::OnceLock::new();
+
+fn ffi_runtime() -> Option<&'static tokio::runtime::Runtime> {
+    FFI_RUNTIME.get_or_init(|| tokio::runtime::Runtime::new().ok()).as_ref()
+}
+
+// Boundary logging, off unless CQT_FFI_LOG is set in the environment or set_ffi_logging_ffi
+// turns it on. Inputs are cut to FFI_LOG_PREVIEW_CHARS so a large scan can't flood the logs.
+static FFI_LOGGING: std::sync::OnceLock<std::sync::atomic::AtomicBool> = std::sync::OnceLock::new();
+const FFI_LOG_PREVIEW_CHARS: usize = 256;
+
+fn ffi_logging() -> &'static std::sync::atomic::AtomicBool {
+    FFI_LOGGING.get_or_init(|| std::sync::atomic::AtomicBool::new(std::env::var_os("CQT_FFI_LOG").is_some()))
+}
+
+fn ffi_log_enabled() -> bool {
+    ffi_logging().load(std::sync::atomic::Ordering::Relaxed)
+}
+
+fn ffi_preview(input: &str) -> String {
+    // At most FFI_LOG_PREVIEW_CHARS characters, noting the full size when cut
+    match input.char_indices().nth(FFI_LOG_PREVIEW_CHARS) {
+        Some((end, _)) => format!("{}... ({} bytes)", &input[..end], input.len()),
+        None => input.to_string(),
+    }
+}
+
+fn ffi_log_entry(function: &str, input: impl fmt::Display) {
+    if ffi_log_enabled() {
+        tracing::info!(target: "arbitrage_core::ffi", function, %input, "ffi call");
+    }
+}
+
+fn ffi_log_exit(function: &str, code: i32, outcome: impl fmt::Display) {
+    if ffi_log_enabled() {
+        tracing::info!(target: "arbitrage_core::ffi", function, code, %outcome, "ffi return");
+    }
+}
+
+// Codes written to the `error_code` out-parameter of FFI functions
+pub const FFI_OK: i32 = 0;
+// Input was not valid UTF-8 or not the expected JSON
+pub const FFI_ERR_PARSE: i32 = 1;
+// The async runtime was unavailable or the operation itself returned an error
+pub const FFI_ERR_RUNTIME: i32 = 2;
+// Validation ran and rejected the opportunity
+pub const FFI_ERR_REJECTED: i32 = 3;
+// A required pointer argument was null
+pub const FFI_ERR_NULL_POINTER: i32 = 4;
+// A caller-provided buffer length did not match the input
+pub const FFI_ERR_LENGTH_MISMATCH: i32 = 5;
+
+unsafe fn set_error_code(error_code: *mut i32, code: i32) {
+    // Callers may pass null when they don't care about the reason
+    if !error_code.is_null() {
+        unsafe { *error_code = code };
+    }
+}
+
+unsafe fn ffi_str<'a>(s: *const i8) -> Result<&'a str, i32> {
+    if s.is_null() {
+        return Err(FFI_ERR_NULL_POINTER);
+    }
+
+    unsafe { std::ffi::CStr::from_ptr(s) }.to_str().map_err(|_| FFI_ERR_PARSE)
+}
+
+/// # Safety
+/// `polygon_rpc` and `base_rpc` must be null or valid, NUL-terminated C strings.
+/// `error_code` must be null or point to a writable `i32`.
+#[no_mangle]
+pub unsafe extern "C" fn create_arbitrage_core(
+    polygon_rpc: *const i8,
+    base_rpc: *const i8,
+    error_code: *mut i32
+) -> *mut ArbitrageCore {
+    let rpcs = unsafe { ffi_str(polygon_rpc).and_then(|polygon_rpc| Ok((polygon_rpc, ffi_str(base_rpc)?))) };
+    let (polygon_rpc, base_rpc) = match rpcs {
+        Ok(rpcs) => rpcs,
+        Err(code) => {
+            ffi_log_exit("create_arbitrage_core", code, "unreadable rpc url");
+            unsafe { set_error_code(error_code, code) };
+            return std::ptr::null_mut();
+        }
+    };
+    ffi_log_entry("create_arbitrage_core", format_args!(
+        "polygon_rpc={} base_rpc={}",
+        redact_url(polygon_rpc), redact_url(base_rpc)
+    ));
+
+    let core = ArbitrageCore::new(polygon_rpc.to_string(), base_rpc.to_string());
+    ffi_log_exit("create_arbitrage_core", FFI_OK, "core created");
+    unsafe { set_error_code(error_code, FFI_OK) };
+    Box::into_raw(Box::new(core))
+}
+
+/// # Safety
+/// `core` must be null or come from `create_arbitrage_core`, and `opportunity_json` must be null
+/// or a valid C string.
+/// `error_code` must be null or point to a writable `i32`.
+#[no_mangle]
+pub unsafe extern "C" fn validate_opportunity_ffi(
+    core: *mut ArbitrageCore, 
+    opportunity_json: *const i8,
+    error_code: *mut i32
+) -> bool {
+    if core.is_null() {
+        ffi_log_exit("validate_opportunity_ffi", FFI_ERR_NULL_POINTER, "null core");
+        unsafe { set_error_code(error_code, FFI_ERR_NULL_POINTER) };
+        return false;
+    }
+
+    let core = unsafe { &*core };
+    let result = unsafe { ffi_str(opportunity_json) }
+        .inspect(|json_str| ffi_log_entry("validate_opportunity_ffi", ffi_preview(json_str)))
+        .and_then(|json_str| serde_json::from_str::<ArbitrageOpportunity>(json_str).map_err(|_| FFI_ERR_PARSE))
+        .and_then(|opportunity| {
+            let runtime = ffi_runtime().ok_or(FFI_ERR_RUNTIME)?;
+            runtime.block_on(core.validate_opportunity(&opportunity)).map_err(|_| FFI_ERR_RUNTIME)
+        });
+
+    let (valid, code) = match result {
+        Ok(true) => (true, FFI_OK),
+        Ok(false) => (false, FFI_ERR_REJECTED),
+        Err(code) => (false, code),
+    };
+    ffi_log_exit("validate_opportunity_ffi", code, format_args!("valid={}", valid));
+    unsafe { set_error_code(error_code, code) };
+    valid
+}
+
+/// Validates a JSON array of opportunities in one call, writing one result per element to
+/// `results_out`. Elements that fail to parse or whose validation errors are written as false.
+///
+/// Returns the number of results written, or -1 with `error_code` set if the input isn't a
+/// JSON array, `len` differs from its length, or the runtime is unavailable.
+///
+/// # Safety
+/// `core` must be null or come from `create_arbitrage_core`, and `opportunities_json` must be
+/// null or a valid C string. `results_out` must be null or point to `len` writable `bool`s.
+/// `error_code` must be null or point to a writable `i32`.
+#[no_mangle]
+pub unsafe extern "C" fn validate_opportunities_batch_ffi(
+    core: *mut ArbitrageCore,
+    opportunities_json: *const i8,
+    results_out: *mut bool,
+    len: usize,
+    error_code: *mut i32
+) -> i64 {
+    if core.is_null() || results_out.is_null() {
+        ffi_log_exit("validate_opportunities_batch_ffi", FFI_ERR_NULL_POINTER, "null core or results_out");
+        unsafe { set_error_code(error_code, FFI_ERR_NULL_POINTER) };
+        return -1;
+    }
+
+    let core = unsafe { &*core };
+    let elements = unsafe { ffi_str(opportunities_json) }
+        .inspect(|json_str| ffi_log_entry("validate_opportunities_batch_ffi", format_args!("len={} json={}", len, ffi_preview(json_str))))
+        .and_then(|json_str| serde_json::from_str::<Vec<serde_json::Value>>(json_str).map_err(|_| FFI_ERR_PARSE))
+        .and_then(|elements| if elements.len() == len { Ok(elements) } else { Err(FFI_ERR_LENGTH_MISMATCH) })
+        .and_then(|elements| Ok((elements, ffi_runtime().ok_or(FFI_ERR_RUNTIME)?)));
+    let (elements, runtime) = match elements {
+        Ok(parsed) => parsed,
+        Err(code) => {
+            ffi_log_exit("validate_opportunities_batch_ffi", code, "no results written");
+            unsafe { set_error_code(error_code, code) };
+            return -1;
+        }
+    };
+
+    let results = runtime.block_on(futures::future::join_all(elements.into_iter().map(|element| async move {
+        match serde_json::from_value::<ArbitrageOpportunity>(element) {
+            Ok(opportunity) => core.validate_opportunity(&opportunity).await.unwrap_or_default(),
+            Err(_) => false,
+        }
+    })));
+
+    let results_out = unsafe { std::slice::from_raw_parts_mut(results_out, len) };
+    results_out.copy_from_slice(&results);
+    let valid = results.iter().filter(|valid| **valid).count();
+    ffi_log_exit("validate_opportunities_batch_ffi", FFI_OK, format_args!("{} of {} valid", valid, results.len()));
+    unsafe { set_error_code(error_code, FFI_OK) };
+    results.len() as i64
+}
+
+/// Scans a JSON array of `PoolInfo` and returns the validated opportunities as a JSON array,
+/// ordered by net profit descending, then source and target pool address.
+///
+/// The returned string is owned by the caller and must be released with `free_string_ffi`,
+/// never with the host's `free`. Returns null, with `error_code` set, if the input is not
+/// valid JSON or the scan could not run.
+///
+/// # Safety
+/// `core` must be null or come from `create_arbitrage_core`, and `pools_json` must be null or a
+/// valid C string.
+/// `error_code` must be null or point to a writable `i32`.
+#[no_mangle]
+pub unsafe extern "C" fn scan_opportunities_ffi(core: *mut ArbitrageCore, pools_json: *const i8, error_code: *mut i32) -> *mut i8 {
+    if core.is_null() {
+        ffi_log_exit("scan_opportunities_ffi", FFI_ERR_NULL_POINTER, "null core");
+        unsafe { set_error_code(error_code, FFI_ERR_NULL_POINTER) };
+        return std::ptr::null_mut();
+    }
+
+    let core = unsafe { &*core };
+    let result = unsafe { ffi_str(pools_json) }
+        .inspect(|json_str| ffi_log_entry("scan_opportunities_ffi", ffi_preview(json_str)))
+        .and_then(|json_str| serde_json::from_str::<Vec<PoolInfo>>(json_str).map_err(|_| FFI_ERR_PARSE))
+        .and_then(|pools| {
+            let runtime = ffi_runtime().ok_or(FFI_ERR_RUNTIME)?;
+            let opportunities = runtime.block_on(core.scan_opportunities(pools));
+            serde_json::to_string(&opportunities)
+                .ok()
+                .and_then(|json| std::ffi::CString::new(json).ok())
+                .ok_or(FFI_ERR_RUNTIME)
+        });
+
+    match result {
+        Ok(json) => {
+            ffi_log_exit("scan_opportunities_ffi", FFI_OK, ffi_preview(&json.to_string_lossy()));
+            unsafe { set_error_code(error_code, FFI_OK) };
+            json.into_raw()
+        }
+        Err(code) => {
+            ffi_log_exit("scan_opportunities_ffi", code, "no result");
+            unsafe { set_error_code(error_code, code) };
+            std::ptr::null_mut()
+        }
+    }
+}
+
+/// # Safety
+/// `s` must be null or a string returned by this library's FFI functions, and must not be
+/// used afterwards.
+#[no_mangle]
+pub unsafe extern "C" fn free_string_ffi(s: *mut i8) {
+    ffi_log_entry("free_string_ffi", format_args!("null={}", s.is_null()));
+    if !s.is_null() {
+        unsafe { drop(std::ffi::CString::from_raw(s)) };
+    }
+}
+
+/// Returns -1.0 if `core` is null.
+///
+/// # Safety
+/// `core` must be null or come from `create_arbitrage_core`.
+#[no_mangle]
+pub unsafe extern "C" fn calculate_optimal_amount_ffi(
+    core: *mut ArbitrageCore,
+    source_liquidity: u64,
+    target_liquidity: u64,
+    price_diff: f64
+) -> f64 {
+    ffi_log_entry("calculate_optimal_amount_ffi", format_args!(
+        "source_liquidity={} target_liquidity={} price_diff={}",
+        source_liquidity, target_liquidity, price_diff
+    ));
+    if core.is_null() {
+        ffi_log_exit("calculate_optimal_amount_ffi", FFI_ERR_NULL_POINTER, "null core");
+        return -1.0;
+    }
+
+    let core = unsafe { &*core };
+    let amount = core.calculate_optimal_amount(source_liquidity as u128, target_liquidity as u128, price_diff);
+    ffi_log_exit("calculate_optimal_amount_ffi", FFI_OK, amount);
+    amount
+}
+
+/// JSON form of `calculate_optimal_amount_ffi`: an object with `amount` (hex wei),
+/// `binding_liquidity` (`"source"` or `"target"`), `price_multiplier` and `clamped`.
+///
+/// The returned string is owned by the caller and must be released with `free_string_ffi`.
+/// Returns null, with `error_code` set, if `core` is null.
+///
+/// # Safety
+/// `core` must be null or come from `create_arbitrage_core`.
+/// `error_code` must be null or point to a writable `i32`.
+#[no_mangle]
+pub unsafe extern "C" fn calculate_optimal_amount_detailed_ffi(
+    core: *mut ArbitrageCore,
+    source_liquidity: u64,
+    target_liquidity: u64,
+    price_diff: f64,
+    error_code: *mut i32
+) -> *mut i8 {
+    ffi_log_entry("calculate_optimal_amount_detailed_ffi", format_args!(
+        "source_liquidity={} target_liquidity={} price_diff={}",
+        source_liquidity, target_liquidity, price_diff
+    ));
+    if core.is_null() {
+        ffi_log_exit("calculate_optimal_amount_detailed_ffi", FFI_ERR_NULL_POINTER, "null core");
+        unsafe { set_error_code(error_code, FFI_ERR_NULL_POINTER) };
+        return std::ptr::null_mut();
+    }
+
+    let core = unsafe { &*core };
+    let sizing = core.calculate_optimal_amount_detailed(source_liquidity as u128, target_liquidity as u128, price_diff);
+    let json = serde_json::to_string(&sizing)
+        .ok()
+        .and_then(|json| std::ffi::CString::new(json).ok());
+
+    match json {
+        Some(json) => {
+            ffi_log_exit("calculate_optimal_amount_detailed_ffi", FFI_OK, json.to_string_lossy());
+            unsafe { set_error_code(error_code, FFI_OK) };
+            json.into_raw()
+        }
+        None => {
+            ffi_log_exit("calculate_optimal_amount_detailed_ffi", FFI_ERR_RUNTIME, "no result");
+            unsafe { set_error_code(error_code, FFI_ERR_RUNTIME) };
+            std::ptr::null_mut()
+        }
+    }
+}
+
+/// # Safety
+/// `core` must be null or come from `create_arbitrage_core`. Passing it to any FFI
+/// function afterwards, including this one, is undefined behaviour.
+#[no_mangle]
+pub unsafe extern "C" fn free_arbitrage_core(core: *mut ArbitrageCore) {
+    ffi_log_entry("free_arbitrage_core", format_args!("null={}", core.is_null()));
+    if !core.is_null() {
+        unsafe { drop(Box::from_raw(core)) };
+    }
+}
+
+/// Turns FFI boundary logging on or off for the whole process, overriding `CQT_FFI_LOG`.
+/// Entries and results are emitted through `tracing` at info level under the
+/// `arbitrage_core::ffi` target, with inputs truncated and RPC URLs redacted to their scheme and host.
+#[no_mangle]
+pub extern "C" fn set_ffi_logging_ffi(enabled: bool) {
+    ffi_logging().store(enabled, std::sync::atomic::Ordering::Relaxed);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn pool(address: &str, network: &str, price: f64) -> PoolInfo {
+        PoolInfo {
+            address: address.to_string(),
+            network: network.to_string(),
+            token0: "0x94ef57abfbff1ad70bd00a921e1d2437f31c1665".to_string(),
+            token1: "0x7ceb23fd6bc0add59e62ac25578270cff1b9f619".to_string(),
+            price,
+            liquidity: 1_000_000 * 10u128.pow(18),
+            fee_tier: 3000,
+            reserve0: None,
+            reserve1: None,
+            decimals: 18,
+            kind: PoolKind::ConstantProduct,
+            dex: String::new(),
+        }
+    }
+
+    fn core() -> ArbitrageCore {
+        ArbitrageCore::new("http://polygon.invalid".to_string(), "http://base.invalid".to_string())
+    }
+
+    fn opportunity(source_pool: PoolInfo, target_pool: PoolInfo, amount: U256) -> ArbitrageOpportunity {
+        // Comfortably profitable, so validation decides on the check under test
+        let required_amount = TokenAmount::new(amount, source_pool.decimals);
+        ArbitrageOpportunity {
+            source_pool,
+            target_pool,
+            profit_potential: required_amount.to_f64() * 0.05,
+            required_amount: required_amount.to_f64(),
+            execution_cost: 0.0,
+            net_profit: required_amount.to_f64() * 0.05,
+            confidence: 0.9,
+            timestamp: unix_now(),
+            direction: TradeDirection::SellSourceBuyTarget,
+            profit_potential_wei: None,
+            required_amount_wei: Some(required_amount),
+            execution_cost_wei: None,
+            gas_quote: None,
+            usd_prices: None,
+            native_entry: false,
+            native_exit: false,
+            urgency: Urgency::Normal,
+            max_slippage_override: None,
+        }
+    }
+
+    #[test]
+    fn build_opportunity_picks_the_reverse_direction_when_only_it_pays() {
+        // token0 is cheaper on the source pool, so only buying there and selling on the
+        // target clears the fees
+        let core = core();
+        let source = pool("0xb1e0b26c31a2e8c3eeBd6d5ff0E386A9c073d24F", "polygon", 1.00);
+        let target = pool("0xd874aeaef376229c8d41d392c9ce272bd41e57d6", "base", 1.05);
+
+        assert!(core.build_directed_opportunity(&source, &target, TradeDirection::SellSourceBuyTarget).is_none());
+        let opportunity = core.build_opportunity(&source, &target).expect("the reverse direction is profitable");
+        assert_eq!(opportunity.direction, TradeDirection::BuySourceSellTarget);
+        assert!(opportunity.net_profit > 0.0);
+    }
+
+    #[tokio::test]
+    async fn slippage_is_decided_in_fixed_point_for_deep_pools() {
+        // amount / (liquidity + amount) is just under 1%, but both sides are far above 2^53
+        // wei and the f64 path rounds the impact to 0.010000000000000002
+        let core = ArbitrageCore::builder()
+            .polygon_rpc("http://polygon.invalid")
+            .max_slippage(0.01)
+            .build()
+            .unwrap();
+        let amount = U256::from_dec_str("475853307378353939963283").unwrap();
+        let liquidity = 99 * 475_853_307_378_353_939_963_283u128 + 1;
+        let source = PoolInfo { liquidity, ..pool("0xb1e0b26c31a2e8c3eeBd6d5ff0E386A9c073d24F", "polygon", 1.05) };
+        let target = pool("0xd874aeaef376229c8d41d392c9ce272bd41e57d6", "polygon", 1.00);
+        let opportunity = opportunity(source.clone(), target, amount);
+
+        let f64_impact = core.pool_price_impact(&source, opportunity.required_amount, SwapAction::Sell);
+        assert!(f64_impact > 0.01, "{}", f64_impact);
+        let fixed_impact = core.pool_price_impact_fixed(&source, amount, SwapAction::Sell);
+        assert!(fixed_impact <= denormalize_amount(0.01, 18), "{}", fixed_impact);
+
+        let outcome = core.validate_opportunity_detailed(&opportunity).await.unwrap();
+        assert_eq!(outcome, ValidationOutcome::Accepted);
+
+        // A trillion wei less liquidity does push the impact past 1%
+        let mut shallower = opportunity.clone();
+        shallower.source_pool.liquidity -= 1_000_000_000_000;
+        let outcome = core.validate_opportunity_detailed(&shallower).await.unwrap();
+        assert!(matches!(outcome, ValidationOutcome::Rejected(RejectionReason::Slippage { .. })), "{:?}", outcome);
+    }
+
+    #[tokio::test]
+    async fn mismatched_pairs_are_rejected() {
+        // USDC/WETH against DAI/WBTC; the same pair in the other token order still matches
+        let core = core();
+        let source = pool("0xb1e0b26c31a2e8c3eeBd6d5ff0E386A9c073d24F", "polygon", 1.05);
+        let other_pair = PoolInfo {
+            token0: "0x8f3cf7ad23cd3cadbd9735aff958023239c6a063".to_string(),
+            token1: "0x1bfd67037b42cf73acf2047067bd4f2c47d9bfd6".to_string(),
+            ..pool("0xd874aeaef376229c8d41d392c9ce272bd41e57d6", "polygon", 1.00)
+        };
+        let mismatched = opportunity(source.clone(), other_pair, U256::exp10(18));
+
+        let outcome = core.validate_opportunity_detailed(&mismatched).await.unwrap();
+        assert!(matches!(outcome, ValidationOutcome::Rejected(RejectionReason::TokenPairMismatch { .. })), "{:?}", outcome);
+        let err = core.validate_opportunity(&mismatched).await.unwrap_err();
+        assert!(matches!(&err, ArbitrageError::ValidationFailed(message) if message.contains(&source.token0)), "{:?}", err);
+
+        let swapped = PoolInfo {
+            token0: source.token1.to_uppercase().replace("0X", "0x"),
+            token1: source.token0.clone(),
+            ..pool("0xd874aeaef376229c8d41d392c9ce272bd41e57d6", "polygon", 1.00)
+        };
+        let outcome = core.validate_opportunity_detailed(&opportunity(source, swapped, U256::exp10(18))).await.unwrap();
+        assert!(!matches!(outcome, ValidationOutcome::Rejected(RejectionReason::TokenPairMismatch { .. })), "{:?}", outcome);
+    }
+
+    #[tokio::test]
+    async fn break_even_spread_covers_fees_and_gas() {
+        // 200,000 gas at 50 gwei is 0.01 of the native coin, $20 at $2,000. On a $10,000 trade that is 0.2%,
+        // on top of the 0.3% and 0.05% pool fees.
+        let network = NetworkConfig { base_gas: 200_000, ..NetworkConfig::polygon("http://polygon.invalid".to_string()) };
+        let core = ArbitrageCore::builder().network("polygon", network).gas_multiplier(1.0).build().unwrap();
+        let gas_cost_usd = core.estimate_gas_cost_usd("polygon", &transaction(U256::from(50_000_000_000u64)), 2000.0, Urgency::Normal).await.unwrap();
+        assert!((gas_cost_usd - 20.0).abs() < 1e-9, "{}", gas_cost_usd);
+
+        let spread = core.break_even_spread(10_000.0, gas_cost_usd, 3000, 500);
+        assert!((spread - 0.55).abs() < 1e-9, "{}", spread);
+        // At exactly that gap the trade nets nothing
+        let net_profit = 10_000.0 * spread / 100.0 - 10_000.0 * 0.0035 - gas_cost_usd;
+        assert!(net_profit.abs() < 1e-9, "{}", net_profit);
+
+        assert_eq!(core.break_even_spread(0.0, gas_cost_usd, 3000, 500), f64::INFINITY);
+    }
+
+    struct RevertAll;
+
+    #[async_trait::async_trait]
+    impl Confirmer for RevertAll {
+        async fn receipt_status(&self, _network: &str, _rpc_url: &str, _tx_hash: web3::types::H256) -> Result<ReceiptStatus, ArbitrageError> {
+            Ok(ReceiptStatus::Reverted { gas_used: U256::from(21_000) })
+        }
+    }
+
+    #[tokio::test]
+    async fn slippage_override_sizes_and_bounds_the_swaps() {
+        // A 1% impact trade, with a 0.5% global tolerance and a 3% override on the opportunity.
+        // Under 0.5% the source leg would be cut down; under 3% it fills in full, and its
+        // minimum output allows for 3%. Only the source swap matters, so it reverts.
+        let executor = Arc::new(MockExecutor::new());
+        let core = ArbitrageCore::builder()
+            .polygon_rpc("http://polygon.invalid")
+            .max_slippage(0.005)
+            .executor(Box::new(executor.clone()))
+            .confirmer(Box::new(RevertAll))
+            .build()
+            .unwrap();
+        let amount = U256::exp10(21);
+        let source = PoolInfo { liquidity: 99 * 10u128.pow(21), ..pool("0xb1e0b26c31a2e8c3eeBd6d5ff0E386A9c073d24F", "polygon", 1.05) };
+        let target = pool("0xd874aeaef376229c8d41d392c9ce272bd41e57d6", "polygon", 1.00);
+        let mut opportunity = opportunity(source.clone(), target, amount);
+        opportunity.max_slippage_override = Some(0.03);
+
+        assert!(core.fill_amount(&source, amount, SwapAction::Sell, 0.005).unwrap() < amount);
+        let result = core.execute_cross_chain_arbitrage(opportunity, &CancellationToken::new()).await;
+        assert!(matches!(result, Err(ArbitrageError::TransactionReverted { .. })), "{:?}", result);
+
+        let source_swap = executor.swaps().into_iter().next().expect("source swap submitted");
+        assert_eq!(source_swap.amount_in, amount);
+        assert_eq!(source_swap.amount_out_min, source_swap.expected_amount_out * 97 / 100);
+    }
+
+    #[tokio::test]
+    async fn concurrent_target_leg_sells_the_source_output() {
+        // Selling 1 token0 at 2.0 on the source pool yields 2 token1, which is what the target
+        // leg must sell; both swaps are submitted before either reverts
+        let executor = Arc::new(MockExecutor::new());
+        let core = ArbitrageCore::builder()
+            .polygon_rpc("http://polygon.invalid")
+            .concurrent_legs(true)
+            .executor(Box::new(executor.clone()))
+            .confirmer(Box::new(RevertAll))
+            .build()
+            .unwrap();
+        let source = pool("0xb1e0b26c31a2e8c3eeBd6d5ff0E386A9c073d24F", "polygon", 2.0);
+        let target = pool("0xd874aeaef376229c8d41d392c9ce272bd41e57d6", "polygon", 1.9);
+        let opportunity = opportunity(source.clone(), target.clone(), U256::exp10(18));
+
+        let result = core.execute_cross_chain_arbitrage(opportunity, &CancellationToken::new()).await;
+        assert!(matches!(result, Err(ArbitrageError::TransactionReverted { .. })), "{:?}", result);
+
+        let swaps = executor.swaps();
+        let source_swap = swaps.iter().find(|swap| swap.pool == source.address).expect("source swap submitted");
+        let target_swap = swaps.iter().find(|swap| swap.pool == target.address).expect("target swap submitted");
+        assert_eq!(source_swap.amount_in, U256::exp10(18));
+        assert_eq!(target_swap.amount_in, U256::exp10(18) * 2);
+    }
+
+    #[tokio::test]
+    async fn dry_run_plans_the_target_leg_with_the_source_output() {
+        let core = ArbitrageCore::builder()
+            .polygon_rpc("http://polygon.invalid")
+            .dry_run(true)
+            .build()
+            .unwrap();
+        let source = pool("0xb1e0b26c31a2e8c3eeBd6d5ff0E386A9c073d24F", "polygon", 2.0);
+        let target = pool("0xd874aeaef376229c8d41d392c9ce272bd41e57d6", "polygon", 1.9);
+        let opportunity = opportunity(source, target, U256::exp10(18));
+
+        let result = core.execute_cross_chain_arbitrage(opportunity, &CancellationToken::new()).await;
+        let Ok(ExecutionResult::DryRun { planned_trades, .. }) = result else { panic!("{:?}", result) };
+        assert_eq!(planned_trades[0].amount, U256::exp10(18));
+        assert_eq!(planned_trades[1].amount, U256::exp10(18) * 2);
+    }
+
+    fn transaction(gas_price: U256) -> SecureTransaction {
+        SecureTransaction {
+            to: Address::zero(),
+            value: U256::zero(),
+            gas_limit: U256::from(21_000),
+            gas_price,
+            data: Vec::new(),
+            nonce: U256::zero(),
+            tx_type: 0,
+            max_fee_per_gas: None,
+            max_priority_fee_per_gas: None,
+        }
+    }
+
+    #[tokio::test]
+    async fn gas_cost_near_u64_max() {
+        // The largest base gas at the largest multiplier still fits; pricing it at an absurd
+        // gas price is what overflows
+        let network = NetworkConfig { base_gas: u64::MAX - 1, ..NetworkConfig::polygon("http://polygon.invalid".to_string()) };
+        let core = ArbitrageCore::builder()
+            .network("polygon", network)
+            .urgency_gas_multiplier(Urgency::High, MAX_GAS_MULTIPLIER)
+            .build()
+            .unwrap();
+
+        let gas = core.estimate_gas_cost("polygon", &transaction(U256::one()), Urgency::High).await.unwrap();
+        assert_eq!(gas, U256::from(u64::MAX - 1) * U256::from(10));
+
+        let cost = core.estimate_gas_cost_usd("polygon", &transaction(U256::from(30_000_000_000u64)), 1.0, Urgency::High).await.unwrap();
+        assert_eq!(cost, u256_to_f64(gas * U256::from(30_000_000_000u64)) / 1e18);
+
+        let overflow = core.estimate_gas_cost_usd("polygon", &transaction(U256::MAX), 1.0, Urgency::High).await;
+        assert!(matches!(overflow, Err(ArbitrageError::Overflow(_))), "{:?}", overflow);
+
+        let source = pool("0xb1e0b26c31a2e8c3eeBd6d5ff0E386A9c073d24F", "polygon", 1.05);
+        let target = pool("0xd874aeaef376229c8d41d392c9ce272bd41e57d6", "polygon", 1.00);
+        let quote = GasQuote {
+            source_gas_price: U256::MAX,
+            target_gas_price: U256::MAX,
+            source_native_price: 1.0,
+            target_native_price: 1.0,
+        };
+        let overflow = core.route_gas_costs(&opportunity(source, target, U256::exp10(18)), &quote).await;
+        assert!(matches!(overflow, Err(ArbitrageError::Overflow(_))), "{:?}", overflow);
     }
 }
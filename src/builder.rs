@@ -0,0 +1,753 @@
+// Fluent configuration for ArbitrageCore
+use rand::rngs::StdRng;
+use rand::SeedableRng;
+use std::collections::HashMap;
+use std::path::PathBuf;
+use std::sync::atomic::AtomicBool;
+use std::sync::{Arc, Mutex};
+use tokio::time::Duration;
+use web3::types::U256;
+
+use crate::config::{bridge_timeouts_ms, millis};
+use crate::{AggLayerBridge, ArbitrageConfig, ArbitrageCore, ArbitrageError, BridgeProvider, CircuitBreaker, Confirmer, DefaultExecutionCostModel, ExecutionCostModel, ExecutionLedger, ExecutionObserver, FlashLoanProvider, GasMultipliers, GasLimitBand, GasOracle, LiquidityTier, MAX_GAS_MULTIPLIER, MAX_ROUTE_LENGTH, Metrics, MockConfirmer, MockExecutor, NetworkConfig, NonceManager, PoolCache, PoolFetcher, PriceCache, PriceOracle, RateLimiter, Signer, SubmissionMode, TradeExecutor, TradeJournal, Urgency};
+
+pub struct ArbitrageCoreBuilder {
+    networks: HashMap<String, NetworkConfig>,
+    max_slippage: f64,
+    max_trade_fraction: f64,
+    max_amount_per_trade: U256,
+    max_capital_per_cycle: U256,
+    max_sandwich_risk: Option<f64>,
+    max_competing_txs: Option<usize>,
+    max_hops: usize,
+    max_bridges: usize,
+    liquidity_tiers: Vec<LiquidityTier>,
+    liquidity_fraction: f64,
+    price_multiplier_min: f64,
+    price_multiplier_max: f64,
+    gas_multipliers: GasMultipliers,
+    max_gas_limit: u64,
+    min_confidence: f64,
+    min_net_profit: f64,
+    min_profit_bps: u32,
+    max_retries: u32,
+    retry_budget: Option<u32>,
+    retry_budget_window: Option<Duration>,
+    retry_base_delay: Duration,
+    max_opportunity_age: Duration,
+    deadline_buffer: Duration,
+    bridge_timeouts: HashMap<(String, String), Duration>,
+    default_bridge_timeout: Duration,
+    min_confirmations: HashMap<String, u64>,
+    rate_limits: HashMap<String, u32>,
+    confirmation_poll_interval: Duration,
+    speed_up_after: Option<Duration>,
+    rpc_timeout: Duration,
+    dry_run: bool,
+    submission_mode: SubmissionMode,
+    concurrent_legs: bool,
+    failure_threshold: u32,
+    failure_window: Duration,
+    cooldown: Duration,
+    dedup_window: Duration,
+    executor: Option<Box<dyn TradeExecutor>>,
+    bridge: Option<Box<dyn BridgeProvider>>,
+    confirmer: Option<Box<dyn Confirmer>>,
+    journal: Option<Box<dyn TradeJournal>>,
+    observer: Option<Box<dyn ExecutionObserver>>,
+    flash_loan: Option<Box<dyn FlashLoanProvider>>,
+    pool_fetcher: Option<Box<dyn PoolFetcher>>,
+    signer: Option<Box<dyn Signer>>,
+    approve_max: bool,
+    use_permits: bool,
+    halt_file: Option<PathBuf>,
+    price_oracle: Option<Box<dyn PriceOracle>>,
+    cost_model: Option<Box<dyn ExecutionCostModel>>,
+    bridge_fee: f64,
+    price_cache_ttl: Duration,
+    pool_cache_ttl: Duration,
+    gas_price_ttl: Duration,
+    seed: Option<u64>,
+}
+
+impl Default for ArbitrageCoreBuilder {
+    fn default() -> Self {
+        Self {
+            networks: HashMap::new(),
+            max_slippage: 0.02, // 2%
+            max_trade_fraction: 0.02, // 2% of the shallower pool
+            max_amount_per_trade: U256::MAX,
+            max_capital_per_cycle: U256::MAX,
+            max_sandwich_risk: None,
+            max_competing_txs: None,
+            max_hops: MAX_ROUTE_LENGTH,
+            max_bridges: MAX_ROUTE_LENGTH - 1,
+            liquidity_tiers: Vec::new(),
+            liquidity_fraction: 0.01, // 1%
+            price_multiplier_min: 0.5,
+            price_multiplier_max: 2.0,
+            gas_multipliers: GasMultipliers::default(),
+            max_gas_limit: GasLimitBand::default().max,
+            min_confidence: 0.7,
+            min_net_profit: 0.0,
+            min_profit_bps: 0,
+            max_retries: 3,
+            retry_budget: None,
+            retry_budget_window: None,
+            retry_base_delay: Duration::from_millis(500),
+            max_opportunity_age: Duration::from_secs(5),
+            deadline_buffer: Duration::from_secs(120),
+            bridge_timeouts: HashMap::new(),
+            default_bridge_timeout: Duration::from_secs(600),
+            min_confirmations: HashMap::new(),
+            rate_limits: HashMap::new(),
+            confirmation_poll_interval: Duration::from_secs(10),
+            speed_up_after: None,
+            rpc_timeout: Duration::from_secs(10),
+            dry_run: false,
+            submission_mode: SubmissionMode::Public,
+            concurrent_legs: false,
+            failure_threshold: 5,
+            failure_window: Duration::from_secs(600),
+            cooldown: Duration::from_secs(300),
+            dedup_window: Duration::from_secs(600),
+            executor: None,
+            bridge: None,
+            confirmer: None,
+            journal: None,
+            observer: None,
+            flash_loan: None,
+            pool_fetcher: None,
+            signer: None,
+            approve_max: false,
+            use_permits: false,
+            halt_file: None,
+            price_oracle: None,
+            cost_model: None,
+            bridge_fee: 0.0,
+            price_cache_ttl: Duration::from_secs(30),
+            pool_cache_ttl: Duration::from_secs(2),
+            gas_price_ttl: Duration::from_secs(12),
+            seed: None,
+        }
+    }
+}
+
+impl ArbitrageCoreBuilder {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn polygon_rpc(self, polygon_rpc: impl Into<String>) -> Self {
+        self.network("polygon", NetworkConfig::polygon(polygon_rpc.into()))
+    }
+
+    pub fn base_rpc(self, base_rpc: impl Into<String>) -> Self {
+        self.network("base", NetworkConfig::base(base_rpc.into()))
+    }
+
+    pub fn network(mut self, name: impl Into<String>, config: NetworkConfig) -> Self {
+        self.networks.insert(name.into(), config);
+        self
+    }
+
+    pub fn max_slippage(mut self, max_slippage: f64) -> Self {
+        self.max_slippage = max_slippage;
+        self
+    }
+
+    pub fn max_trade_fraction(mut self, max_trade_fraction: f64) -> Self {
+        // Upper bound on a proposed trade, as a fraction of the shallower pool's liquidity
+        self.max_trade_fraction = max_trade_fraction;
+        self
+    }
+
+    pub fn max_amount_per_trade(mut self, max_amount_per_trade: U256) -> Self {
+        // Raw source-token amount; larger candidates are scaled down when scanned and
+        // rejected in validation
+        self.max_amount_per_trade = max_amount_per_trade;
+        self
+    }
+
+    pub fn max_capital_per_cycle(mut self, max_capital_per_cycle: U256) -> Self {
+        // Total raw amount a scan may propose and executions may deploy before the next scan
+        self.max_capital_per_cycle = max_capital_per_cycle;
+        self
+    }
+
+    pub fn max_sandwich_risk(mut self, max_sandwich_risk: f64) -> Self {
+        // Publicly submitted trades scoring above this sandwich risk fail validation
+        self.max_sandwich_risk = Some(max_sandwich_risk);
+        self
+    }
+
+    pub fn max_competing_txs(mut self, max_competing_txs: usize) -> Self {
+        // Opportunities whose pools have more pending transactions than this fail validation
+        self.max_competing_txs = Some(max_competing_txs);
+        self
+    }
+
+    pub fn max_hops(mut self, max_hops: usize) -> Self {
+        // Routes with more swaps than this are rejected, however profitable they look
+        self.max_hops = max_hops;
+        self
+    }
+
+    pub fn max_bridges(mut self, max_bridges: usize) -> Self {
+        // Routes crossing networks more often than this are rejected
+        self.max_bridges = max_bridges;
+        self
+    }
+
+    pub fn liquidity_tiers(mut self, liquidity_tiers: Vec<LiquidityTier>) -> Self {
+        // Confidence multipliers by pool depth, applied as opportunities are built. Breakpoints
+        // depend on what the pools trade, so there are none by default.
+        self.liquidity_tiers = liquidity_tiers;
+        self
+    }
+
+    pub fn liquidity_fraction(mut self, liquidity_fraction: f64) -> Self {
+        // Base trade size as a fraction of the shallower pool, before the price multiplier
+        self.liquidity_fraction = liquidity_fraction;
+        self
+    }
+
+    pub fn price_multiplier_range(mut self, min: f64, max: f64) -> Self {
+        // Bounds on how far the price difference can scale the base trade size
+        self.price_multiplier_min = min;
+        self.price_multiplier_max = max;
+        self
+    }
+
+    pub fn gas_multiplier(self, gas_multiplier: f64) -> Self {
+        // The Normal-urgency multiplier, used wherever no override applies
+        self.urgency_gas_multiplier(Urgency::Normal, gas_multiplier)
+    }
+
+    pub fn urgency_gas_multiplier(mut self, urgency: Urgency, gas_multiplier: f64) -> Self {
+        self.gas_multipliers.set_default(urgency, gas_multiplier);
+        self
+    }
+
+    pub fn network_gas_multiplier(mut self, network: impl Into<String>, urgency: Urgency, gas_multiplier: f64) -> Self {
+        // Overrides the urgency's multiplier on one network, e.g. a lower buffer on an L2
+        self.gas_multipliers.set_network(network, urgency, gas_multiplier);
+        self
+    }
+
+    pub fn max_gas_limit(mut self, max_gas_limit: u64) -> Self {
+        // Transactions above this gas limit are rejected; networks can set their own band
+        self.max_gas_limit = max_gas_limit;
+        self
+    }
+
+    pub fn min_confidence(mut self, min_confidence: f64) -> Self {
+        self.min_confidence = min_confidence;
+        self
+    }
+
+    pub fn min_net_profit(mut self, min_net_profit: f64) -> Self {
+        self.min_net_profit = min_net_profit;
+        self
+    }
+
+    pub fn min_profit_bps(mut self, min_profit_bps: u32) -> Self {
+        // Net profit floor in basis points of the trade size, checked alongside min_net_profit
+        self.min_profit_bps = min_profit_bps;
+        self
+    }
+
+    pub fn max_retries(mut self, max_retries: u32) -> Self {
+        self.max_retries = max_retries;
+        self
+    }
+
+    pub fn retry_budget(mut self, retry_budget: u32) -> Self {
+        // Retries allowed across every call one execute_cross_chain_arbitrage makes; max_retries
+        // still bounds each call
+        self.retry_budget = Some(retry_budget);
+        self
+    }
+
+    pub fn retry_budget_window(mut self, retry_budget_window: Duration) -> Self {
+        // Nothing is retried once an execution has been running this long
+        self.retry_budget_window = Some(retry_budget_window);
+        self
+    }
+
+    pub fn retry_base_delay(mut self, retry_base_delay: Duration) -> Self {
+        self.retry_base_delay = retry_base_delay;
+        self
+    }
+
+    pub fn max_opportunity_age(mut self, max_opportunity_age: Duration) -> Self {
+        // Opportunities whose timestamp is older than this fail validation
+        self.max_opportunity_age = max_opportunity_age;
+        self
+    }
+
+    pub fn deadline_buffer(mut self, deadline_buffer: Duration) -> Self {
+        // Slack added on top of the expected wait before a leg is considered stale
+        self.deadline_buffer = deadline_buffer;
+        self
+    }
+
+    pub fn bridge_timeout(mut self, source: impl Into<String>, target: impl Into<String>, timeout: Duration) -> Self {
+        // How long to wait for a bridge transfer on this route before giving up
+        self.bridge_timeouts.insert((source.into(), target.into()), timeout);
+        self
+    }
+
+    pub fn default_bridge_timeout(mut self, default_bridge_timeout: Duration) -> Self {
+        self.default_bridge_timeout = default_bridge_timeout;
+        self
+    }
+
+    pub fn min_confirmations(mut self, network: impl Into<String>, min_confirmations: u64) -> Self {
+        // Blocks that must be mined on top of a receipt on this network before it counts as confirmed
+        self.min_confirmations.insert(network.into(), min_confirmations);
+        self
+    }
+
+    pub fn rate_limit(mut self, network: impl Into<String>, requests_per_second: u32) -> Self {
+        // Ceiling on RPC calls per second to this network's endpoint
+        self.rate_limits.insert(network.into(), requests_per_second);
+        self
+    }
+
+    pub fn confirmation_poll_interval(mut self, confirmation_poll_interval: Duration) -> Self {
+        self.confirmation_poll_interval = confirmation_poll_interval;
+        self
+    }
+
+    pub fn speed_up_after(mut self, speed_up_after: Duration) -> Self {
+        // Replace a locally signed transaction at a higher gas price once it has been pending this long
+        self.speed_up_after = Some(speed_up_after);
+        self
+    }
+
+    pub fn rpc_timeout(mut self, rpc_timeout: Duration) -> Self {
+        // Longest any single RPC call may take before failing with RpcTimeout
+        self.rpc_timeout = rpc_timeout;
+        self
+    }
+
+    pub fn dry_run(mut self, dry_run: bool) -> Self {
+        // Validate and estimate as usual, but never submit trades or bridge transfers
+        self.dry_run = dry_run;
+        self
+    }
+
+    pub fn submission_mode(mut self, submission_mode: SubmissionMode) -> Self {
+        // Private relays keep swaps out of the public mempool, away from sandwich bots
+        self.submission_mode = submission_mode;
+        self
+    }
+
+    pub fn concurrent_legs(mut self, concurrent_legs: bool) -> Self {
+        // Submit both swaps of a single-network opportunity at once; cross-chain routes stay sequential
+        self.concurrent_legs = concurrent_legs;
+        self
+    }
+
+    pub fn failure_threshold(mut self, failure_threshold: u32) -> Self {
+        // Consecutive execution failures that open the circuit breaker
+        self.failure_threshold = failure_threshold;
+        self
+    }
+
+    pub fn failure_window(mut self, failure_window: Duration) -> Self {
+        self.failure_window = failure_window;
+        self
+    }
+
+    pub fn cooldown(mut self, cooldown: Duration) -> Self {
+        self.cooldown = cooldown;
+        self
+    }
+
+    pub fn dedup_window(mut self, dedup_window: Duration) -> Self {
+        // How long a completed execution's result is returned for repeat calls
+        self.dedup_window = dedup_window;
+        self
+    }
+
+    pub fn executor(mut self, executor: Box<dyn TradeExecutor>) -> Self {
+        self.executor = Some(executor);
+        self
+    }
+
+    pub fn bridge(mut self, bridge: Box<dyn BridgeProvider>) -> Self {
+        self.bridge = Some(bridge);
+        self
+    }
+
+    pub fn confirmer(mut self, confirmer: Box<dyn Confirmer>) -> Self {
+        // RpcConfirmer polls real receipts; the default mock confirms at random
+        self.confirmer = Some(confirmer);
+        self
+    }
+
+    pub fn journal(mut self, journal: Box<dyn TradeJournal>) -> Self {
+        // Every execution attempt, successful or not, is recorded here
+        self.journal = Some(journal);
+        self
+    }
+
+    pub fn observer(mut self, observer: Box<dyn ExecutionObserver>) -> Self {
+        // Told about each stage of execute_cross_chain_arbitrage as it happens
+        self.observer = Some(observer);
+        self
+    }
+
+    pub fn flash_loan_provider(mut self, flash_loan: Box<dyn FlashLoanProvider>) -> Self {
+        // Enables execute_flash_arbitrage for single-network routes
+        self.flash_loan = Some(flash_loan);
+        self
+    }
+
+    pub fn pool_fetcher(mut self, pool_fetcher: Box<dyn PoolFetcher>) -> Self {
+        self.pool_fetcher = Some(pool_fetcher);
+        self
+    }
+
+    pub fn signer(mut self, signer: Box<dyn Signer>) -> Self {
+        // Swaps are signed locally and handed to the executor as raw transactions
+        self.signer = Some(signer);
+        self
+    }
+
+    pub fn approve_max(mut self, approve_max: bool) -> Self {
+        // Approve U256::MAX on the first shortfall instead of exactly the trade amount
+        self.approve_max = approve_max;
+        self
+    }
+
+    pub fn use_permits(mut self, use_permits: bool) -> Self {
+        // Bundle an EIP-2612 permit into the swap through the router's selfPermit and
+        // multicall(bytes[]), for tokens that support it, instead of a separate approve
+        self.use_permits = use_permits;
+        self
+    }
+
+    pub fn halt_file(mut self, halt_file: impl Into<PathBuf>) -> Self {
+        // The core stays halted while this file exists, e.g. after touch halt on the host
+        self.halt_file = Some(halt_file.into());
+        self
+    }
+
+    pub fn price_oracle(mut self, price_oracle: Box<dyn PriceOracle>) -> Self {
+        // Prices profit and gas in USD for opportunities that don't carry usd_prices
+        self.price_oracle = Some(price_oracle);
+        self
+    }
+
+    pub fn execution_cost_model(mut self, cost_model: Box<dyn ExecutionCostModel>) -> Self {
+        // Recomputes execution_cost before a trade runs; the default sums pool fees, gas and bridge_fee
+        self.cost_model = Some(cost_model);
+        self
+    }
+
+    pub fn bridge_fee(mut self, bridge_fee: f64) -> Self {
+        // Flat cost of one bridge transfer in the profit token, for the default cost model
+        self.bridge_fee = bridge_fee;
+        self
+    }
+
+    pub fn price_cache_ttl(mut self, price_cache_ttl: Duration) -> Self {
+        // How long an oracle price is reused before querying the oracle again
+        self.price_cache_ttl = price_cache_ttl;
+        self
+    }
+
+    pub fn pool_cache_ttl(mut self, pool_cache_ttl: Duration) -> Self {
+        // How long fetched pool state is reused before querying the pool again
+        self.pool_cache_ttl = pool_cache_ttl;
+        self
+    }
+
+    pub fn gas_price_ttl(mut self, gas_price_ttl: Duration) -> Self {
+        // How long a fee-history based gas suggestion is reused per network
+        self.gas_price_ttl = gas_price_ttl;
+        self
+    }
+
+    pub fn seed(mut self, seed: u64) -> Self {
+        // Seeds the mock RNG so hashes and confirmation timing are reproducible
+        self.seed = Some(seed);
+        self
+    }
+
+    pub fn config(mut self, config: ArbitrageConfig) -> Self {
+        // Replaces every setting the config covers; backends already set are kept
+        self.networks = config.networks.into_iter().collect();
+        self.max_slippage = config.max_slippage;
+        self.max_trade_fraction = config.max_trade_fraction;
+        self.max_amount_per_trade = config.max_amount_per_trade;
+        self.max_capital_per_cycle = config.max_capital_per_cycle;
+        self.max_sandwich_risk = config.max_sandwich_risk;
+        self.max_competing_txs = config.max_competing_txs;
+        self.max_hops = config.max_hops;
+        self.max_bridges = config.max_bridges;
+        self.liquidity_tiers = config.liquidity_tiers;
+        self.liquidity_fraction = config.liquidity_fraction;
+        self.price_multiplier_min = config.price_multiplier_min;
+        self.price_multiplier_max = config.price_multiplier_max;
+        self.gas_multipliers = config.gas_multipliers;
+        self.max_gas_limit = config.max_gas_limit;
+        self.min_confidence = config.min_confidence;
+        self.min_net_profit = config.min_net_profit;
+        self.min_profit_bps = config.min_profit_bps;
+        self.max_retries = config.max_retries;
+        self.retry_budget = config.retry_budget;
+        self.retry_budget_window = config.retry_budget_window_ms.map(Duration::from_millis);
+        self.retry_base_delay = Duration::from_millis(config.retry_base_delay_ms);
+        self.max_opportunity_age = Duration::from_millis(config.max_opportunity_age_ms);
+        self.deadline_buffer = Duration::from_millis(config.deadline_buffer_ms);
+        self.bridge_timeouts = config.bridge_timeouts_ms.into_iter()
+            .flat_map(|(source, targets)| targets.into_iter()
+                .map(move |(target, ms)| ((source.clone(), target), Duration::from_millis(ms))))
+            .collect();
+        self.default_bridge_timeout = Duration::from_millis(config.default_bridge_timeout_ms);
+        self.min_confirmations = config.min_confirmations.into_iter().collect();
+        self.rate_limits = config.rate_limits.into_iter().collect();
+        self.confirmation_poll_interval = Duration::from_millis(config.confirmation_poll_interval_ms);
+        self.speed_up_after = config.speed_up_after_ms.map(Duration::from_millis);
+        self.rpc_timeout = Duration::from_millis(config.rpc_timeout_ms);
+        self.dry_run = config.dry_run;
+        self.submission_mode = config.submission_mode;
+        self.concurrent_legs = config.concurrent_legs;
+        self.failure_threshold = config.failure_threshold;
+        self.failure_window = Duration::from_millis(config.failure_window_ms);
+        self.cooldown = Duration::from_millis(config.cooldown_ms);
+        self.dedup_window = Duration::from_millis(config.dedup_window_ms);
+        self.approve_max = config.approve_max;
+        self.use_permits = config.use_permits;
+        self.halt_file = config.halt_file;
+        self.pool_cache_ttl = Duration::from_millis(config.pool_cache_ttl_ms);
+        self.gas_price_ttl = Duration::from_millis(config.gas_price_ttl_ms);
+        self.price_cache_ttl = Duration::from_millis(config.price_cache_ttl_ms);
+        self.bridge_fee = config.bridge_fee;
+        self
+    }
+
+    pub(crate) fn to_config(&self) -> ArbitrageConfig {
+        ArbitrageConfig {
+            networks: self.networks.clone().into_iter().collect(),
+            max_slippage: self.max_slippage,
+            max_trade_fraction: self.max_trade_fraction,
+            max_amount_per_trade: self.max_amount_per_trade,
+            max_capital_per_cycle: self.max_capital_per_cycle,
+            max_sandwich_risk: self.max_sandwich_risk,
+            max_competing_txs: self.max_competing_txs,
+            max_hops: self.max_hops,
+            max_bridges: self.max_bridges,
+            liquidity_tiers: self.liquidity_tiers.clone(),
+            liquidity_fraction: self.liquidity_fraction,
+            price_multiplier_min: self.price_multiplier_min,
+            price_multiplier_max: self.price_multiplier_max,
+            gas_multipliers: self.gas_multipliers.clone(),
+            max_gas_limit: self.max_gas_limit,
+            min_confidence: self.min_confidence,
+            min_net_profit: self.min_net_profit,
+            min_profit_bps: self.min_profit_bps,
+            max_retries: self.max_retries,
+            retry_budget: self.retry_budget,
+            retry_budget_window_ms: self.retry_budget_window.map(millis),
+            retry_base_delay_ms: millis(self.retry_base_delay),
+            max_opportunity_age_ms: millis(self.max_opportunity_age),
+            deadline_buffer_ms: millis(self.deadline_buffer),
+            bridge_timeouts_ms: bridge_timeouts_ms(&self.bridge_timeouts),
+            default_bridge_timeout_ms: millis(self.default_bridge_timeout),
+            min_confirmations: self.min_confirmations.clone().into_iter().collect(),
+            rate_limits: self.rate_limits.clone().into_iter().collect(),
+            confirmation_poll_interval_ms: millis(self.confirmation_poll_interval),
+            speed_up_after_ms: self.speed_up_after.map(millis),
+            rpc_timeout_ms: millis(self.rpc_timeout),
+            dry_run: self.dry_run,
+            submission_mode: self.submission_mode.clone(),
+            concurrent_legs: self.concurrent_legs,
+            failure_threshold: self.failure_threshold,
+            failure_window_ms: millis(self.failure_window),
+            cooldown_ms: millis(self.cooldown),
+            dedup_window_ms: millis(self.dedup_window),
+            approve_max: self.approve_max,
+            use_permits: self.use_permits,
+            halt_file: self.halt_file.clone(),
+            pool_cache_ttl_ms: millis(self.pool_cache_ttl),
+            gas_price_ttl_ms: millis(self.gas_price_ttl),
+            price_cache_ttl_ms: millis(self.price_cache_ttl),
+            bridge_fee: self.bridge_fee,
+        }
+    }
+
+    pub fn build(self) -> Result<ArbitrageCore, ArbitrageError> {
+        if self.networks.is_empty() {
+            return Err(ArbitrageError::InvalidConfig("at least one network is required".to_string()));
+        }
+
+        if !(self.max_slippage > 0.0 && self.max_slippage <= 1.0) {
+            return Err(ArbitrageError::InvalidConfig(format!(
+                "max_slippage must be within (0.0, 1.0], got {}",
+                self.max_slippage
+            )));
+        }
+
+        if !(self.max_trade_fraction > 0.0 && self.max_trade_fraction <= 1.0) {
+            return Err(ArbitrageError::InvalidConfig(format!(
+                "max_trade_fraction must be within (0.0, 1.0], got {}",
+                self.max_trade_fraction
+            )));
+        }
+
+        if let Some(max_sandwich_risk) = self.max_sandwich_risk {
+            if !(0.0..=1.0).contains(&max_sandwich_risk) {
+                return Err(ArbitrageError::InvalidConfig(format!(
+                    "max_sandwich_risk must be within 0.0..=1.0, got {}",
+                    max_sandwich_risk
+                )));
+            }
+        }
+
+        if !(self.liquidity_fraction > 0.0 && self.liquidity_fraction <= 1.0) {
+            return Err(ArbitrageError::InvalidConfig(format!(
+                "liquidity_fraction must be within (0.0, 1.0], got {}",
+                self.liquidity_fraction
+            )));
+        }
+
+        if !(self.price_multiplier_min > 0.0 && self.price_multiplier_min <= self.price_multiplier_max && self.price_multiplier_max.is_finite()) {
+            return Err(ArbitrageError::InvalidConfig(format!(
+                "price multiplier range must satisfy 0.0 < min <= max, got {}..{}",
+                self.price_multiplier_min, self.price_multiplier_max
+            )));
+        }
+
+        if let Some(gas_multiplier) = self.gas_multipliers.values().find(|m| !(*m >= 1.0 && *m <= MAX_GAS_MULTIPLIER)) {
+            return Err(ArbitrageError::InvalidConfig(format!(
+                "gas_multiplier must be between 1.0 and {}, got {}",
+                MAX_GAS_MULTIPLIER, gas_multiplier
+            )));
+        }
+
+        let default_band = GasLimitBand { max: self.max_gas_limit, ..GasLimitBand::default() };
+        let bands = std::iter::once(("default", default_band)).chain(
+            self.networks.iter().filter_map(|(name, config)| Some((name.as_str(), config.gas_limit_band?)))
+        );
+        for (name, band) in bands {
+            if !(band.min >= GasLimitBand::default().min && band.min <= band.max) {
+                return Err(ArbitrageError::InvalidConfig(format!(
+                    "{} gas limit band must satisfy {} <= min <= max, got {}..{}",
+                    name, GasLimitBand::default().min, band.min, band.max
+                )));
+            }
+        }
+
+        if self.max_hops == 0 {
+            return Err(ArbitrageError::InvalidConfig("max_hops must be at least 1".to_string()));
+        }
+
+        if let Some(tier) = self.liquidity_tiers.iter().find(|tier| !(tier.multiplier > 0.0 && tier.multiplier.is_finite())) {
+            return Err(ArbitrageError::InvalidConfig(format!(
+                "liquidity tier multipliers must be finite and above 0.0, got {} at {}",
+                tier.multiplier, tier.min_liquidity
+            )));
+        }
+
+        if !(self.bridge_fee >= 0.0 && self.bridge_fee.is_finite()) {
+            return Err(ArbitrageError::InvalidConfig(format!("bridge_fee must be a finite amount of at least 0.0, got {}", self.bridge_fee)));
+        }
+
+        if self.rpc_timeout.is_zero() {
+            return Err(ArbitrageError::InvalidConfig("rpc_timeout must be greater than zero".to_string()));
+        }
+
+        if self.failure_threshold == 0 {
+            return Err(ArbitrageError::InvalidConfig("failure_threshold must be at least 1".to_string()));
+        }
+
+        // depth_adjusted_confidence picks the last tier reached
+        let mut liquidity_tiers = self.liquidity_tiers.clone();
+        liquidity_tiers.sort_by_key(|tier| tier.min_liquidity);
+
+        let rng = match self.seed {
+            Some(seed) => StdRng::seed_from_u64(seed),
+            None => StdRng::from_entropy(),
+        };
+        let rng = Arc::new(Mutex::new(rng));
+
+        let endpoints = self.networks.iter()
+            .map(|(name, config)| (name.clone(), config.rpc_url.clone()))
+            .collect();
+
+        let mut core = ArbitrageCore {
+            networks: self.networks,
+            max_slippage: self.max_slippage,
+            max_trade_fraction: self.max_trade_fraction,
+            max_amount_per_trade: self.max_amount_per_trade,
+            max_capital_per_cycle: self.max_capital_per_cycle,
+            deployed_capital: Mutex::new(U256::zero()),
+            max_sandwich_risk: self.max_sandwich_risk,
+            max_competing_txs: self.max_competing_txs,
+            max_hops: self.max_hops,
+            max_bridges: self.max_bridges,
+            liquidity_tiers,
+            liquidity_fraction: self.liquidity_fraction,
+            price_multiplier_min: self.price_multiplier_min,
+            price_multiplier_max: self.price_multiplier_max,
+            gas_multipliers: self.gas_multipliers,
+            gas_limit_band: GasLimitBand { max: self.max_gas_limit, ..GasLimitBand::default() },
+            min_confidence: 0.7,
+            min_net_profit: self.min_net_profit,
+            min_profit_bps: self.min_profit_bps,
+            net_profit_tolerance: 0.01, // 1% of profit potential
+            max_retries: self.max_retries,
+            retry_budget: self.retry_budget,
+            retry_budget_window: self.retry_budget_window,
+            retry_base_delay: self.retry_base_delay,
+            max_opportunity_age: self.max_opportunity_age,
+            deadline_buffer: self.deadline_buffer,
+            bridge_timeouts: self.bridge_timeouts,
+            default_bridge_timeout: self.default_bridge_timeout,
+            min_confirmations: self.min_confirmations,
+            confirmation_poll_interval: self.confirmation_poll_interval,
+            speed_up_after: self.speed_up_after,
+            rpc_timeout: self.rpc_timeout,
+            dry_run: self.dry_run,
+            submission_mode: self.submission_mode,
+            concurrent_legs: self.concurrent_legs,
+            bridge: self.bridge.unwrap_or_else(|| Box::new(AggLayerBridge::with_rng(rng.clone()))),
+            confirmer: self.confirmer.unwrap_or_else(|| Box::new(MockConfirmer::with_rng(rng.clone()))),
+            executor: self.executor.unwrap_or_else(|| Box::new(MockExecutor::new())),
+            journal: self.journal,
+            observer: self.observer,
+            flash_loan: self.flash_loan,
+            pool_fetcher: self.pool_fetcher,
+            signer: self.signer,
+            approve_max: self.approve_max,
+            use_permits: self.use_permits,
+            halted: Arc::new(AtomicBool::new(false)),
+            halt_file: self.halt_file,
+            price_oracle: self.price_oracle,
+            cost_model: self.cost_model.unwrap_or_else(|| Box::new(DefaultExecutionCostModel::new(self.bridge_fee))),
+            bridge_fee: self.bridge_fee,
+            price_cache: PriceCache::new(self.price_cache_ttl),
+            pool_cache: PoolCache::new(self.pool_cache_ttl),
+            gas_oracle: GasOracle::new(self.gas_price_ttl),
+            rate_limiter: RateLimiter::new(),
+            nonce_manager: NonceManager::new(endpoints),
+            replaceable: Mutex::new(HashMap::new()),
+            permits: Mutex::new(HashMap::new()),
+            metrics: Metrics::new(),
+            circuit_breaker: CircuitBreaker::new(self.failure_threshold, self.failure_window, self.cooldown),
+            executions: ExecutionLedger::new(self.dedup_window),
+            rng,
+        };
+        core.set_min_confidence(self.min_confidence)?;
+        for (network, requests_per_second) in &self.rate_limits {
+            core.set_rate_limit(network, *requests_per_second);
+        }
+
+        Ok(core)
+    }
+}
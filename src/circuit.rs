@@ -0,0 +1,93 @@
+// Circuit breaker guarding trade execution for CryptoQuest Arbitrage Bot
+use std::sync::Mutex;
+use std::time::Instant;
+use tokio::time::Duration;
+
+use crate::ArbitrageError;
+
+#[derive(Debug)]
+pub struct CircuitBreaker {
+    failure_threshold: u32,
+    failure_window: Duration,
+    cooldown: Duration,
+    state: Mutex<CircuitState>,
+}
+
+#[derive(Debug, Default)]
+struct CircuitState {
+    consecutive_failures: u32,
+    first_failure_at: Option<Instant>,
+    opened_at: Option<Instant>,
+}
+
+impl CircuitBreaker {
+    pub fn new(failure_threshold: u32, failure_window: Duration, cooldown: Duration) -> Self {
+        Self {
+            failure_threshold,
+            failure_window,
+            cooldown,
+            state: Mutex::new(CircuitState::default()),
+        }
+    }
+
+    pub fn failure_threshold(&self) -> u32 {
+        self.failure_threshold
+    }
+
+    pub fn failure_window(&self) -> Duration {
+        self.failure_window
+    }
+
+    pub fn cooldown(&self) -> Duration {
+        self.cooldown
+    }
+
+    pub fn check(&self) -> Result<(), ArbitrageError> {
+        // Reject while open; close again automatically once the cooldown has elapsed
+        let mut state = self.state.lock().unwrap();
+
+        if let Some(opened_at) = state.opened_at {
+            let elapsed = opened_at.elapsed();
+            if elapsed < self.cooldown {
+                return Err(ArbitrageError::CircuitOpen { retry_in: self.cooldown - elapsed });
+            }
+
+            *state = CircuitState::default();
+        }
+
+        Ok(())
+    }
+
+    pub fn record_success(&self) {
+        *self.state.lock().unwrap() = CircuitState::default();
+    }
+
+    pub fn record_failure(&self) {
+        let mut state = self.state.lock().unwrap();
+        let now = Instant::now();
+
+        // Failures spread further apart than the window start a fresh streak
+        match state.first_failure_at {
+            Some(first) if now.duration_since(first) <= self.failure_window => {
+                state.consecutive_failures += 1;
+            }
+            _ => {
+                state.consecutive_failures = 1;
+                state.first_failure_at = Some(now);
+            }
+        }
+
+        if state.consecutive_failures >= self.failure_threshold && state.opened_at.is_none() {
+            tracing::warn!(failures = state.consecutive_failures, "circuit breaker opened");
+            state.opened_at = Some(now);
+        }
+    }
+
+    pub fn reset(&self) {
+        *self.state.lock().unwrap() = CircuitState::default();
+    }
+
+    pub fn is_open(&self) -> bool {
+        self.check().is_err()
+    }
+}
@@ -0,0 +1,113 @@
+// Serializable bot configuration for CryptoQuest Arbitrage Bot
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
+use std::fs;
+use std::path::{Path, PathBuf};
+use tokio::time::Duration;
+use web3::types::U256;
+
+use crate::{ArbitrageCoreBuilder, ArbitrageError, GasMultipliers, LiquidityTier, NetworkConfig, SubmissionMode};
+
+// Everything ArbitrageCoreBuilder takes except the pluggable backends (executor, bridge,
+// confirmer, journal, observer, flash loans, pool fetcher, signer, price oracle, cost
+// model), which stay in code. Durations are in milliseconds; maps are ordered so saved files
+// diff cleanly. Missing keys take the builder's defaults.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ArbitrageConfig {
+    pub networks: BTreeMap<String, NetworkConfig>,
+    pub max_slippage: f64,
+    pub max_trade_fraction: f64,
+    pub max_amount_per_trade: U256,
+    pub max_capital_per_cycle: U256,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max_sandwich_risk: Option<f64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max_competing_txs: Option<usize>,
+    pub max_hops: usize,
+    pub max_bridges: usize,
+    pub liquidity_tiers: Vec<LiquidityTier>,
+    pub liquidity_fraction: f64,
+    pub price_multiplier_min: f64,
+    pub price_multiplier_max: f64,
+    pub gas_multipliers: GasMultipliers,
+    pub max_gas_limit: u64,
+    pub min_confidence: f64,
+    pub min_net_profit: f64,
+    pub min_profit_bps: u32,
+    pub max_retries: u32,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub retry_budget: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub retry_budget_window_ms: Option<u64>,
+    pub retry_base_delay_ms: u64,
+    pub max_opportunity_age_ms: u64,
+    pub deadline_buffer_ms: u64,
+    // Source network, then target network
+    pub bridge_timeouts_ms: BTreeMap<String, BTreeMap<String, u64>>,
+    pub default_bridge_timeout_ms: u64,
+    pub min_confirmations: BTreeMap<String, u64>,
+    pub rate_limits: BTreeMap<String, u32>,
+    pub confirmation_poll_interval_ms: u64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub speed_up_after_ms: Option<u64>,
+    pub rpc_timeout_ms: u64,
+    pub dry_run: bool,
+    pub submission_mode: SubmissionMode,
+    pub concurrent_legs: bool,
+    pub failure_threshold: u32,
+    pub failure_window_ms: u64,
+    pub cooldown_ms: u64,
+    pub dedup_window_ms: u64,
+    pub approve_max: bool,
+    pub use_permits: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub halt_file: Option<PathBuf>,
+    pub pool_cache_ttl_ms: u64,
+    pub gas_price_ttl_ms: u64,
+    pub price_cache_ttl_ms: u64,
+    pub bridge_fee: f64,
+}
+
+impl ArbitrageConfig {
+    pub fn from_toml_str(toml: &str) -> Result<Self, ArbitrageError> {
+        toml::from_str(toml).map_err(|err| ArbitrageError::InvalidConfig(err.to_string()))
+    }
+
+    pub fn to_toml_string(&self) -> Result<String, ArbitrageError> {
+        toml::to_string_pretty(self).map_err(|err| ArbitrageError::InvalidConfig(err.to_string()))
+    }
+
+    pub fn load(path: impl AsRef<Path>) -> Result<Self, ArbitrageError> {
+        let path = path.as_ref();
+        let toml = fs::read_to_string(path)
+            .map_err(|err| ArbitrageError::InvalidConfig(format!("cannot read {}: {}", path.display(), err)))?;
+
+        Self::from_toml_str(&toml)
+    }
+
+    pub fn save(&self, path: impl AsRef<Path>) -> Result<(), ArbitrageError> {
+        let path = path.as_ref();
+        fs::write(path, self.to_toml_string()?)
+            .map_err(|err| ArbitrageError::InvalidConfig(format!("cannot write {}: {}", path.display(), err)))
+    }
+}
+
+impl Default for ArbitrageConfig {
+    fn default() -> Self {
+        ArbitrageCoreBuilder::default().to_config()
+    }
+}
+
+pub(crate) fn millis(duration: Duration) -> u64 {
+    duration.as_millis().try_into().unwrap_or(u64::MAX)
+}
+
+pub(crate) fn bridge_timeouts_ms(timeouts: &HashMap<(String, String), Duration>) -> BTreeMap<String, BTreeMap<String, u64>> {
+    // Route-keyed timeouts nested by source then target, as ArbitrageConfig stores them
+    let mut nested: BTreeMap<String, BTreeMap<String, u64>> = BTreeMap::new();
+    for ((source, target), timeout) in timeouts {
+        nested.entry(source.clone()).or_default().insert(target.clone(), millis(*timeout));
+    }
+    nested
+}
@@ -0,0 +1,105 @@
+// Transaction confirmation checks for CryptoQuest Arbitrage Bot
+use async_trait::async_trait;
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use web3::types::{H256, U256};
+
+use crate::ArbitrageError;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ReceiptStatus {
+    // Not mined yet, or dropped from the node's view
+    Pending,
+    // Succeeded, with this many blocks mined on top of the receipt's block; gas_paid is
+    // gas used times the effective gas price, in the network's native wei
+    Mined { confirmations: u64, gas_paid: U256 },
+    // Mined with status 0; the gas was still spent
+    Reverted { gas_used: U256 },
+}
+
+#[async_trait]
+pub trait Confirmer: Send + Sync {
+    async fn receipt_status(&self, network: &str, rpc_url: &str, tx_hash: H256) -> Result<ReceiptStatus, ArbitrageError>;
+}
+
+// Reads eth_getTransactionReceipt and the current block number from the network's RPC
+#[derive(Debug, Default)]
+pub struct RpcConfirmer;
+
+impl RpcConfirmer {
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+#[async_trait]
+impl Confirmer for RpcConfirmer {
+    async fn receipt_status(&self, _network: &str, rpc_url: &str, tx_hash: H256) -> Result<ReceiptStatus, ArbitrageError> {
+        let transport = web3::transports::Http::new(rpc_url)?;
+        let eth = web3::Web3::new(transport).eth();
+
+        let Some(receipt) = eth.transaction_receipt(tx_hash).await? else {
+            return Ok(ReceiptStatus::Pending);
+        };
+        let Some(block_number) = receipt.block_number else {
+            return Ok(ReceiptStatus::Pending);
+        };
+        if receipt.status.map(|status| status.is_zero()).unwrap_or(false) {
+            return Ok(ReceiptStatus::Reverted { gas_used: receipt.gas_used.unwrap_or_default() });
+        }
+
+        let current_block = eth.block_number().await?;
+        let gas_used = receipt.gas_used.unwrap_or_default();
+        Ok(ReceiptStatus::Mined {
+            confirmations: current_block.saturating_sub(block_number).as_u64(),
+            gas_paid: gas_used.saturating_mul(receipt.effective_gas_price.unwrap_or_default()),
+        })
+    }
+}
+
+// Default confirmer while bridge and executor are mocks: each check confirms with a
+// 10% chance, unless a status was scripted for the hash
+#[derive(Debug)]
+pub struct MockConfirmer {
+    rng: Arc<Mutex<StdRng>>,
+    statuses: Mutex<HashMap<H256, ReceiptStatus>>,
+}
+
+impl MockConfirmer {
+    pub fn new() -> Self {
+        Self::with_rng(Arc::new(Mutex::new(StdRng::from_entropy())))
+    }
+
+    pub fn with_rng(rng: Arc<Mutex<StdRng>>) -> Self {
+        Self { rng, statuses: Mutex::new(HashMap::new()) }
+    }
+
+    pub fn set_status(&self, tx_hash: H256, status: ReceiptStatus) {
+        self.statuses.lock().unwrap().insert(tx_hash, status);
+    }
+}
+
+impl Default for MockConfirmer {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[async_trait]
+impl Confirmer for MockConfirmer {
+    async fn receipt_status(&self, _network: &str, _rpc_url: &str, tx_hash: H256) -> Result<ReceiptStatus, ArbitrageError> {
+        if let Some(status) = self.statuses.lock().unwrap().get(&tx_hash) {
+            return Ok(*status);
+        }
+
+        let confirmed = self.rng.lock().unwrap().gen::<f64>() > 0.9;
+        Ok(if confirmed {
+            // Comfortably past any configured confirmation depth
+            ReceiptStatus::Mined { confirmations: 64, gas_paid: U256::zero() }
+        } else {
+            ReceiptStatus::Pending
+        })
+    }
+}
@@ -0,0 +1,57 @@
+// Execution cost models for CryptoQuest Arbitrage Bot
+use web3::types::U256;
+
+use crate::{u256_to_f64, ArbitrageOpportunity, FEE_TIER_DENOMINATOR};
+
+pub trait ExecutionCostModel: Send + Sync {
+    // Everything executing the opportunity costs, in its profit token. gas_costs are the wei
+    // paid per leg: source swap and bridge on the source network, then the target swap on the
+    // target network. Empty when no gas quote was available.
+    fn estimate(&self, opportunity: &ArbitrageOpportunity, gas_costs: &[U256]) -> f64;
+}
+
+// Pool fees on both legs, gas converted through the opportunity's USD prices or its gas
+// quote, and a flat fee per bridge transfer
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct DefaultExecutionCostModel {
+    bridge_fee: f64,
+}
+
+impl DefaultExecutionCostModel {
+    pub fn new(bridge_fee: f64) -> Self {
+        // bridge_fee is in the profit token and only charged when the legs are bridged
+        Self { bridge_fee }
+    }
+
+    pub fn bridge_fee(&self) -> f64 {
+        self.bridge_fee
+    }
+}
+
+impl ExecutionCostModel for DefaultExecutionCostModel {
+    fn estimate(&self, opportunity: &ArbitrageOpportunity, gas_costs: &[U256]) -> f64 {
+        let fee_tiers = (opportunity.source_pool.fee_tier + opportunity.target_pool.fee_tier) as f64;
+        let pool_fees = opportunity.required_amount * fee_tiers / FEE_TIER_DENOMINATOR;
+
+        // Native price of each network in profit tokens
+        let native_prices = match (opportunity.usd_prices, opportunity.gas_quote) {
+            (Some(prices), _) if prices.profit_token > 0.0 => {
+                Some((prices.source_native / prices.profit_token, prices.target_native / prices.profit_token))
+            }
+            (_, Some(quote)) => Some((quote.source_native_price, quote.target_native_price)),
+            _ => None,
+        };
+        let gas = match native_prices {
+            Some((source_price, target_price)) => gas_costs.iter().enumerate()
+                .map(|(leg, wei)| {
+                    let price = if leg < 2 { source_price } else { target_price };
+                    u256_to_f64(*wei) / 1e18 * price
+                })
+                .sum(),
+            None => 0.0,
+        };
+
+        let bridge_fee = if opportunity.needs_bridge() { self.bridge_fee } else { 0.0 };
+        pool_fees + gas + bridge_fee
+    }
+}
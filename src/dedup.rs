@@ -0,0 +1,96 @@
+// Duplicate execution guard for CryptoQuest Arbitrage Bot
+use std::collections::HashMap;
+use std::sync::Mutex;
+use tokio::time::{Duration, Instant};
+
+use crate::{ArbitrageError, ExecutionResult};
+
+#[derive(Debug)]
+enum LedgerEntry {
+    InFlight,
+    Completed { at: Instant, result: Box<ExecutionResult> },
+}
+
+// Tracks opportunity ids that are executing or recently executed, so a caller
+// retrying after a timeout can't run the same trade twice
+#[derive(Debug)]
+pub struct ExecutionLedger {
+    retention: Duration,
+    entries: Mutex<HashMap<String, LedgerEntry>>,
+}
+
+pub(crate) enum Admission<'a> {
+    Started(ExecutionGuard<'a>),
+    Completed(ExecutionResult),
+}
+
+impl ExecutionLedger {
+    pub fn new(retention: Duration) -> Self {
+        Self {
+            retention,
+            entries: Mutex::new(HashMap::new()),
+        }
+    }
+
+    pub fn retention(&self) -> Duration {
+        self.retention
+    }
+
+    pub(crate) fn begin(&self, id: &str) -> Result<Admission<'_>, ArbitrageError> {
+        let mut entries = self.entries.lock().unwrap();
+        entries.retain(|_, entry| match entry {
+            LedgerEntry::InFlight => true,
+            LedgerEntry::Completed { at, .. } => at.elapsed() < self.retention,
+        });
+
+        match entries.get(id) {
+            Some(LedgerEntry::InFlight) => Err(ArbitrageError::DuplicateExecution(id.to_string())),
+            Some(LedgerEntry::Completed { result, .. }) => Ok(Admission::Completed(result.as_ref().clone())),
+            None => {
+                entries.insert(id.to_string(), LedgerEntry::InFlight);
+                Ok(Admission::Started(ExecutionGuard { ledger: self, id: id.to_string(), finished: false }))
+            }
+        }
+    }
+
+    pub fn is_in_flight(&self, id: &str) -> bool {
+        matches!(self.entries.lock().unwrap().get(id), Some(LedgerEntry::InFlight))
+    }
+}
+
+impl Default for ExecutionLedger {
+    fn default() -> Self {
+        Self::new(Duration::from_secs(600))
+    }
+}
+
+// Releases the in-flight marker even if the execution future is dropped part way
+pub(crate) struct ExecutionGuard<'a> {
+    ledger: &'a ExecutionLedger,
+    id: String,
+    finished: bool,
+}
+
+impl ExecutionGuard<'_> {
+    pub(crate) fn finish(mut self, result: &Result<ExecutionResult, ArbitrageError>) {
+        // Only live executions are remembered; failures and dry runs may be retried
+        let mut entries = self.ledger.entries.lock().unwrap();
+        match result {
+            Ok(result) if !result.is_dry_run() => {
+                entries.insert(self.id.clone(), LedgerEntry::Completed { at: Instant::now(), result: Box::new(result.clone()) });
+            }
+            _ => {
+                entries.remove(&self.id);
+            }
+        }
+        self.finished = true;
+    }
+}
+
+impl Drop for ExecutionGuard<'_> {
+    fn drop(&mut self) {
+        if !self.finished {
+            self.ledger.entries.lock().unwrap().remove(&self.id);
+        }
+    }
+}
@@ -0,0 +1,140 @@
+// Error types for CryptoQuest Arbitrage Bot
+use thiserror::Error;
+use tokio::time::Duration;
+use web3::types::U256;
+
+#[derive(Debug, Error)]
+pub enum ArbitrageError {
+    #[error("invalid address: {0}")]
+    InvalidAddress(String),
+
+    #[error("slippage {actual} exceeds limit {limit}")]
+    SlippageExceeded { limit: f64, actual: f64 },
+
+    #[error("gas limit too low: {0}")]
+    GasLimitTooLow(u64),
+
+    #[error("gas limit too high: {0}")]
+    GasLimitTooHigh(u64),
+
+    #[error("gas price too high: {0}")]
+    GasPriceTooHigh(u64),
+
+    #[error("transaction {tx_hash} on {route} not confirmed within {seconds}s")]
+    ConfirmationTimeout { tx_hash: String, route: String, seconds: u64 },
+
+    #[error("transaction {tx_hash} reverted on {network} after using {gas_used} gas")]
+    TransactionReverted { tx_hash: String, network: String, gas_used: U256 },
+
+    #[error("validation failed: {0}")]
+    ValidationFailed(String),
+
+    #[error("bridge failed: {0}")]
+    BridgeFailed(String),
+
+    #[error("invalid configuration: {0}")]
+    InvalidConfig(String),
+
+    #[error("unknown network: {0}")]
+    UnknownNetwork(String),
+
+    #[error("{network} rpc serves chain id {actual}, expected {expected}")]
+    ChainIdMismatch { network: String, expected: u64, actual: u64 },
+
+    #[error("invalid event log: {0}")]
+    InvalidLog(String),
+
+    #[error("signing failed: {0}")]
+    Signing(String),
+
+    #[error("transaction rejected on the signing device")]
+    UserRejected,
+
+    #[error("private relay rejected transaction: {0}")]
+    RelayRejected(String),
+
+    #[error("rpc error: {0}")]
+    Rpc(String),
+
+    #[error("{network} rpc did not respond in time")]
+    RpcTimeout { network: String },
+
+    #[error("replacement gas price {replacement} must be at least 12.5% above {original}")]
+    ReplacementUnderpriced { original: U256, replacement: U256 },
+
+    #[error("simulated transaction reverted: {reason}")]
+    SimulationReverted { reason: String },
+
+    #[error("opportunity is {age_secs}s old, limit is {max_age_secs}s")]
+    StaleOpportunity { age_secs: u64, max_age_secs: u64 },
+
+    #[error("trade of {requested} exceeds the {available} capital left this cycle")]
+    CapitalLimitExceeded { requested: U256, available: U256 },
+
+    #[error("deadline {deadline} passed at {now}")]
+    DeadlineExceeded { deadline: u64, now: u64 },
+
+    #[error("opportunity {0} is already being executed")]
+    DuplicateExecution(String),
+
+    #[error("arbitrage cancelled before the source trade was submitted")]
+    Cancelled,
+
+    #[error("arithmetic overflow: {0}")]
+    Overflow(String),
+
+    #[error("circuit breaker open, retry in {retry_in:?}")]
+    CircuitOpen { retry_in: Duration },
+
+    #[error("execution halted by the kill switch")]
+    Halted,
+}
+
+impl ArbitrageError {
+    pub fn is_transient(&self) -> bool {
+        // Failures worth retrying; everything else reflects the opportunity or configuration
+        matches!(self, ArbitrageError::Rpc(_) | ArbitrageError::RpcTimeout { .. } | ArbitrageError::ConfirmationTimeout { .. })
+    }
+}
+
+impl From<web3::Error> for ArbitrageError {
+    fn from(err: web3::Error) -> Self {
+        // Transport errors quote the request URL, which can carry an API key
+        ArbitrageError::Rpc(redact_urls(&err.to_string()))
+    }
+}
+
+pub fn redact_url(url: &str) -> String {
+    // Scheme and host only, for logs and errors: providers put API keys in the path, the
+    // query or the userinfo, percent-encoded or not, so all of those become ***
+    let Some((scheme, rest)) = url.split_once("://") else {
+        return "***".to_string();
+    };
+    let authority_end = rest.find(['/', '?', '#']).unwrap_or(rest.len());
+    let (authority, tail) = rest.split_at(authority_end);
+    let host = authority.rsplit_once('@').map_or(authority, |(_, host)| host);
+
+    if authority.contains('@') || !tail.trim_start_matches('/').is_empty() {
+        format!("{}://{}/***", scheme, host)
+    } else {
+        format!("{}://{}", scheme, host)
+    }
+}
+
+pub(crate) fn redact_urls(text: &str) -> String {
+    // Every URL in free text, such as a reqwest error, passed through redact_url
+    const SCHEMES: [&str; 4] = ["http://", "https://", "ws://", "wss://"];
+    let mut redacted = String::with_capacity(text.len());
+    let mut rest = text;
+
+    while let Some(start) = SCHEMES.iter().filter_map(|scheme| rest.find(scheme)).min() {
+        let end = rest[start..]
+            .find(|c: char| c.is_whitespace() || matches!(c, ')' | ']' | '>' | '"' | '\''))
+            .map_or(rest.len(), |len| start + len);
+        redacted.push_str(&rest[..start]);
+        redacted.push_str(&redact_url(&rest[start..end]));
+        rest = &rest[end..];
+    }
+    redacted.push_str(rest);
+    redacted
+}
@@ -0,0 +1,221 @@
+// Pool event decoding for CryptoQuest Arbitrage Bot
+use web3::types::{Log, H256, U256};
+
+use crate::{default_decimals, u256_to_f64, ArbitrageError, PoolInfo, PoolKind};
+
+// 2^96, the fixed-point scale of Uniswap V3 sqrt prices
+const Q96: f64 = 79_228_162_514_264_337_593_543_950_336.0;
+
+pub(crate) fn sync_topic() -> H256 {
+    // Uniswap V2 pairs emit Sync with the new reserves after every swap
+    H256(web3::signing::keccak256(b"Sync(uint112,uint112)"))
+}
+
+pub(crate) fn v2_swap_topic() -> H256 {
+    H256(web3::signing::keccak256(b"Swap(address,uint256,uint256,uint256,uint256,address)"))
+}
+
+pub(crate) fn v3_swap_topic() -> H256 {
+    H256(web3::signing::keccak256(b"Swap(address,address,int256,int256,uint160,uint128,int24)"))
+}
+
+fn word(data: &[u8], index: usize) -> Option<U256> {
+    data.get(index * 32..(index + 1) * 32).map(U256::from_big_endian)
+}
+
+pub(crate) fn sqrt_price_to_price(sqrt_price_x96: U256) -> f64 {
+    let sqrt_price = u256_to_f64(sqrt_price_x96) / Q96;
+    sqrt_price * sqrt_price
+}
+
+pub(crate) fn decode_swap_log(log: &Log, fee_tier: u32) -> Result<PoolInfo, ArbitrageError> {
+    // Only what the log itself carries: network and tokens are left empty for the caller to fill
+    let topic0 = *log.topics.first()
+        .ok_or_else(|| ArbitrageError::InvalidLog("log has no topics".to_string()))?;
+    let data = &log.data.0;
+    let field = |index: usize| word(data, index)
+        .ok_or_else(|| ArbitrageError::InvalidLog(format!("log data too short for field {}", index)));
+
+    let mut pool = PoolInfo {
+        address: format!("{:?}", log.address),
+        network: String::new(),
+        token0: String::new(),
+        token1: String::new(),
+        price: 0.0,
+        liquidity: 0,
+        fee_tier,
+        reserve0: None,
+        reserve1: None,
+        decimals: default_decimals(),
+        kind: PoolKind::ConstantProduct,
+        dex: String::new(),
+    };
+
+    if topic0 == sync_topic() {
+        let reserve0 = field(0)?.low_u128();
+        let reserve1 = field(1)?.low_u128();
+        if reserve0 == 0 {
+            return Err(ArbitrageError::InvalidLog("Sync log with empty reserve0".to_string()));
+        }
+
+        // V2 liquidity is the geometric mean of the reserves
+        pool.price = reserve1 as f64 / reserve0 as f64;
+        pool.liquidity = (reserve0 as f64 * reserve1 as f64).sqrt() as u128;
+        pool.reserve0 = Some(reserve0);
+        pool.reserve1 = Some(reserve1);
+    } else if topic0 == v2_swap_topic() {
+        // V2 Swap only has the traded amounts, so price is this swap's execution price and
+        // liquidity stays unknown (0) until the accompanying Sync log
+        let (amount0_in, amount1_in) = (field(0)?, field(1)?);
+        let (amount0_out, amount1_out) = (field(2)?, field(3)?);
+
+        pool.price = if !amount0_in.is_zero() {
+            u256_to_f64(amount1_out) / u256_to_f64(amount0_in)
+        } else if !amount0_out.is_zero() {
+            u256_to_f64(amount1_in) / u256_to_f64(amount0_out)
+        } else {
+            return Err(ArbitrageError::InvalidLog("V2 Swap log without token0 amounts".to_string()));
+        };
+    } else if topic0 == v3_swap_topic() {
+        pool.price = sqrt_price_to_price(field(2)?);
+        pool.liquidity = field(3)?.low_u128();
+        pool.kind = PoolKind::ConcentratedV3;
+    } else {
+        return Err(ArbitrageError::InvalidLog(format!("unsupported event topic {:?}", topic0)));
+    }
+
+    Ok(pool)
+}
+
+pub(crate) fn apply_pool_log(pool: &PoolInfo, log: &Log) -> Option<PoolInfo> {
+    // New state for a known pool; keeps its identity and any fields the log didn't carry
+    let decoded = decode_swap_log(log, pool.fee_tier).ok()?;
+    let mut updated = pool.clone();
+
+    updated.price = decoded.price;
+    if decoded.liquidity > 0 {
+        updated.liquidity = decoded.liquidity;
+    }
+    if decoded.reserve0.is_some() {
+        updated.reserve0 = decoded.reserve0;
+        updated.reserve1 = decoded.reserve1;
+    }
+
+    Some(updated)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Logs in the shape eth_getLogs returns them, for the USDC/WETH pairs: token0 is USDC
+    // (6 decimals), token1 WETH (18), at 4e8 raw WETH per raw USDC (2500 USDC per ETH)
+    fn rpc_log(address: &str, topics: &[H256], data: &str) -> Log {
+        serde_json::from_value(serde_json::json!({
+            "address": address,
+            "topics": topics,
+            "data": format!("0x{}", data),
+            "blockHash": "0x9c8f7a4d1b1fb8b9b6e7d59dc1e4c3f7b0c2c593c6b6cb00e8a1a4f6ab0d3d21",
+            "blockNumber": "0x1286f3c",
+            "transactionHash": "0x5e8d3a0e5f6b5b1cbd7f4f1c6b7f2c1a9e1a4a9c7b5d2f0e3c6a8b9d0e1f2a3b",
+            "transactionIndex": "0x5",
+            "logIndex": "0x1a",
+            "transactionLogIndex": null,
+            "logType": null,
+            "removed": false,
+        })).unwrap()
+    }
+
+    fn address_topic(address: &str) -> H256 {
+        H256::from(address.parse::<web3::types::Address>().unwrap())
+    }
+
+    const ROUTER: &str = "0x7a250d5630b4cf539739df2c5dacb4c659f2488d";
+
+    fn v2_pool() -> PoolInfo {
+        PoolInfo {
+            address: "0xb4e16d0168e52d35cacd2c6185b44281ec28c9dc".to_string(),
+            network: "ethereum".to_string(),
+            token0: "0xa0b86991c6218b36c1d19d4a2e9eb0ce3606eb48".to_string(),
+            token1: "0xc02aaa39b223fe8d0a0e5c4f27ead9083c756cc2".to_string(),
+            price: 3.9e8,
+            liquidity: 1,
+            fee_tier: 3000,
+            reserve0: Some(1),
+            reserve1: Some(1),
+            decimals: 18,
+            kind: PoolKind::ConstantProduct,
+            dex: String::new(),
+        }
+    }
+
+    #[test]
+    fn sync_log_updates_reserves() {
+        // Sync(30,000,000 USDC, 12,000 WETH)
+        let log = rpc_log("0xb4e16d0168e52d35cacd2c6185b44281ec28c9dc", &[sync_topic()], concat!(
+            "00000000000000000000000000000000000000000000000000001b48eb57e000",
+            "00000000000000000000000000000000000000000000028a857425466f800000",
+        ));
+        let updated = apply_pool_log(&v2_pool(), &log).unwrap();
+
+        assert_eq!(updated.reserve0, Some(30_000_000_000_000));
+        assert_eq!(updated.reserve1, Some(12_000 * 10u128.pow(18)));
+        assert_eq!(updated.price, 4e8);
+        assert_eq!(updated.liquidity, (3e13f64 * 1.2e22).sqrt() as u128);
+        assert_eq!(updated.token0, v2_pool().token0);
+    }
+
+    #[test]
+    fn v2_swap_log_prices_the_trade_and_keeps_reserves() {
+        // 2,500 USDC in, 1 WETH out
+        let topics = [v2_swap_topic(), address_topic(ROUTER), address_topic(ROUTER)];
+        let log = rpc_log("0xb4e16d0168e52d35cacd2c6185b44281ec28c9dc", &topics, concat!(
+            "000000000000000000000000000000000000000000000000000000009502f900",
+            "0000000000000000000000000000000000000000000000000000000000000000",
+            "0000000000000000000000000000000000000000000000000000000000000000",
+            "0000000000000000000000000000000000000000000000000de0b6b3a7640000",
+        ));
+        let updated = apply_pool_log(&v2_pool(), &log).unwrap();
+
+        assert_eq!(updated.price, 4e8);
+        // Swap carries no reserves; the pool's last known state stays
+        assert_eq!((updated.reserve0, updated.reserve1, updated.liquidity), (Some(1), Some(1), 1));
+    }
+
+    #[test]
+    fn v3_swap_log_updates_sqrt_price_and_liquidity() {
+        // +2,500 USDC, -1 WETH, sqrtPriceX96 = 20000 * 2^96, liquidity 2e19, tick 198079
+        let topics = [v3_swap_topic(), address_topic(ROUTER), address_topic(ROUTER)];
+        let log = rpc_log("0x88e6a0c2ddd26feeb64f039a2c41296fcb3f5640", &topics, concat!(
+            "000000000000000000000000000000000000000000000000000000009502f900",
+            "fffffffffffffffffffffffffffffffffffffffffffffffff21f494c589c0000",
+            "0000000000000000000000000000000000004e20000000000000000000000000",
+            "000000000000000000000000000000000000000000000001158e460913d00000",
+            "00000000000000000000000000000000000000000000000000000000000305bf",
+        ));
+        let pool = PoolInfo {
+            address: "0x88e6a0c2ddd26feeb64f039a2c41296fcb3f5640".to_string(),
+            fee_tier: 500,
+            kind: PoolKind::ConcentratedV3,
+            reserve0: None,
+            reserve1: None,
+            ..v2_pool()
+        };
+
+        let decoded = decode_swap_log(&log, 500).unwrap();
+        assert_eq!(decoded.kind, PoolKind::ConcentratedV3);
+        assert_eq!(decoded.fee_tier, 500);
+
+        let updated = apply_pool_log(&pool, &log).unwrap();
+        assert!((updated.price - 4e8).abs() < 1e-3, "{}", updated.price);
+        assert_eq!(updated.liquidity, 20_000_000_000_000_000_000);
+        assert_eq!(updated.reserve0, None);
+    }
+
+    #[test]
+    fn unknown_topic_is_rejected() {
+        let log = rpc_log("0xb4e16d0168e52d35cacd2c6185b44281ec28c9dc", &[H256::zero()], "");
+        assert!(matches!(decode_swap_log(&log, 3000), Err(ArbitrageError::InvalidLog(_))));
+        assert!(apply_pool_log(&v2_pool(), &log).is_none());
+    }
+}
@@ -0,0 +1,212 @@
+// Trade execution backends for CryptoQuest Arbitrage Bot
+use async_trait::async_trait;
+use ethabi::Token;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fmt;
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+use web3::types::{Address, U256};
+
+use crate::error::redact_urls;
+use crate::{redact_url, ArbitrageError, RawTransaction};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SwapAction {
+    Buy,
+    Sell,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SwapRequest {
+    pub network: String,
+    pub pool: String,
+    // Contract the swap is sent to: the router of the pool's DEX, or the pool address itself
+    pub router: String,
+    pub action: SwapAction,
+    pub amount_in: U256,
+    pub expected_amount_out: U256,
+    // Executors must make the swap revert on-chain below this output
+    pub amount_out_min: U256,
+    // Unix timestamp after which the swap must not be submitted
+    pub deadline: u64,
+    // Set by the core when a signer is configured; executors broadcast it as-is
+    pub signed_transaction: Option<RawTransaction>,
+}
+
+impl SwapRequest {
+    pub fn encode_router_calldata(&self, path: &[Address], recipient: Address) -> Vec<u8> {
+        // UniswapV2-style router call, so an underfilled swap reverts instead of settling at a bad price
+        let signature = b"swapExactTokensForTokens(uint256,uint256,address[],address,uint256)";
+        let selector = &web3::signing::keccak256(signature)[..4];
+        let params = ethabi::encode(&[
+            Token::Uint(self.amount_in),
+            Token::Uint(self.amount_out_min),
+            Token::Array(path.iter().map(|token| Token::Address(*token)).collect()),
+            Token::Address(recipient),
+            Token::Uint(U256::from(self.deadline)),
+        ]);
+
+        [selector, &params[..]].concat()
+    }
+}
+
+#[derive(Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SubmissionMode {
+    #[default]
+    Public,
+    // Flashbots-style relay that keeps the transaction out of the public mempool
+    PrivateRelay { endpoint: String },
+}
+
+impl fmt::Debug for SubmissionMode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        // Relay endpoints can carry an API key as well
+        match self {
+            SubmissionMode::Public => write!(f, "Public"),
+            SubmissionMode::PrivateRelay { endpoint } => f.debug_struct("PrivateRelay")
+                .field("endpoint", &redact_url(endpoint))
+                .finish(),
+        }
+    }
+}
+
+impl fmt::Display for SubmissionMode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SubmissionMode::Public => write!(f, "public"),
+            SubmissionMode::PrivateRelay { .. } => write!(f, "private_relay"),
+        }
+    }
+}
+
+pub async fn send_private_transaction(endpoint: &str, raw_transaction: &[u8]) -> Result<String, ArbitrageError> {
+    // For executors that sign locally: eth_sendPrivateTransaction, with any relay error treated as a rejection
+    let transport = web3::transports::Http::new(endpoint)?;
+    let params = serde_json::json!({ "tx": format!("0x{}", hex::encode(raw_transaction)) });
+    let response = web3::Transport::execute(&transport, "eth_sendPrivateTransaction", vec![params])
+        .await
+        .map_err(|err| ArbitrageError::RelayRejected(redact_urls(&err.to_string())))?;
+
+    response.as_str()
+        .map(str::to_string)
+        .ok_or_else(|| ArbitrageError::RelayRejected(format!("unexpected relay response: {}", response)))
+}
+
+pub(crate) fn quote_amount_out(amount_in: U256, price: f64, action: SwapAction) -> U256 {
+    // Pool price is token1 per token0: selling token0 multiplies by it, buying divides
+    let amount_in = crate::u256_to_f64(amount_in);
+    let amount_out = match action {
+        SwapAction::Sell => amount_in * price,
+        SwapAction::Buy if price > 0.0 => amount_in / price,
+        SwapAction::Buy => 0.0,
+    };
+
+    crate::denormalize_amount(amount_out, 0)
+}
+
+#[async_trait]
+pub trait TradeExecutor: Send + Sync {
+    async fn swap(&self, request: &SwapRequest) -> Result<String, ArbitrageError>;
+
+    // Submit through a private relay instead of the public mempool.
+    // RelayRejected makes the core fall back to a public swap.
+    async fn swap_private(&self, _request: &SwapRequest, endpoint: &str) -> Result<String, ArbitrageError> {
+        Err(ArbitrageError::RelayRejected(format!("executor cannot submit to {}", redact_url(endpoint))))
+    }
+}
+
+// Lets callers keep a handle on an executor (e.g. to inspect a mock) after handing it to the core
+#[async_trait]
+impl<T: TradeExecutor + ?Sized> TradeExecutor for Arc<T> {
+    async fn swap(&self, request: &SwapRequest) -> Result<String, ArbitrageError> {
+        (**self).swap(request).await
+    }
+
+    async fn swap_private(&self, request: &SwapRequest, endpoint: &str) -> Result<String, ArbitrageError> {
+        (**self).swap_private(request, endpoint).await
+    }
+}
+
+// Records every swap and returns a hash derived from the request and call order,
+// so identical call sequences always yield identical hashes
+#[derive(Debug, Default)]
+pub struct MockExecutor {
+    sequence: AtomicU64,
+    swaps: Mutex<Vec<SwapRequest>>,
+    mock_prices: Mutex<HashMap<String, f64>>,
+    reject_private: AtomicBool,
+    private_swaps: AtomicU64,
+}
+
+impl MockExecutor {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn swaps(&self) -> Vec<SwapRequest> {
+        self.swaps.lock().unwrap().clone()
+    }
+
+    pub fn set_mock_price(&self, pool: &str, price: f64) {
+        // Swaps on this pool fill at the given price, reverting if that falls below amount_out_min
+        self.mock_prices.lock().unwrap().insert(pool.to_string(), price);
+    }
+
+    pub fn set_relay_rejects(&self, reject: bool) {
+        // Makes swap_private fail with RelayRejected, to exercise the public fallback
+        self.reject_private.store(reject, Ordering::SeqCst);
+    }
+
+    pub fn private_swaps(&self) -> u64 {
+        self.private_swaps.load(Ordering::SeqCst)
+    }
+}
+
+#[async_trait]
+impl TradeExecutor for MockExecutor {
+    async fn swap(&self, request: &SwapRequest) -> Result<String, ArbitrageError> {
+        tracing::info!(
+            action = ?request.action,
+            network = %request.network,
+            pool = %request.pool,
+            amount_in = %request.amount_in,
+            amount_out_min = %request.amount_out_min,
+            "executing trade"
+        );
+
+        let mock_price = self.mock_prices.lock().unwrap().get(&request.pool).copied();
+        if let Some(price) = mock_price {
+            let filled = quote_amount_out(request.amount_in, price, request.action);
+            if filled < request.amount_out_min {
+                let expected = crate::u256_to_f64(request.expected_amount_out).max(f64::MIN_POSITIVE);
+                return Err(ArbitrageError::SlippageExceeded {
+                    limit: 1.0 - crate::u256_to_f64(request.amount_out_min) / expected,
+                    actual: 1.0 - crate::u256_to_f64(filled) / expected,
+                });
+            }
+        }
+
+        let sequence = self.sequence.fetch_add(1, Ordering::SeqCst);
+        let preimage = format!(
+            "{}:{}:{}:{:?}:{}",
+            request.network, request.pool, request.amount_in, request.action, sequence
+        );
+        let hash = web3::signing::keccak256(preimage.as_bytes());
+
+        self.swaps.lock().unwrap().push(request.clone());
+
+        Ok(format!("0x{}", hex::encode(hash)))
+    }
+    async fn swap_private(&self, request: &SwapRequest, endpoint: &str) -> Result<String, ArbitrageError> {
+        if self.reject_private.load(Ordering::SeqCst) {
+            return Err(ArbitrageError::RelayRejected(format!("mock relay {} rejected the swap", redact_url(endpoint))));
+        }
+
+        let tx_hash = self.swap(request).await?;
+        self.private_swaps.fetch_add(1, Ordering::SeqCst);
+        Ok(tx_hash)
+    }
+}
@@ -0,0 +1,64 @@
+// Flash-loan providers for CryptoQuest Arbitrage Bot
+use async_trait::async_trait;
+use std::sync::atomic::{AtomicU64, Ordering};
+use web3::types::{Address, U256};
+
+use crate::ArbitrageError;
+
+#[async_trait]
+pub trait FlashLoanProvider: Send + Sync {
+    // Borrow, run callback_data in the receiver contract and repay amount plus fee, all in one transaction
+    async fn flash_loan(&self, token: Address, amount: U256, callback_data: Vec<u8>) -> Result<String, ArbitrageError>;
+
+    fn loan_fee(&self, amount: U256) -> U256;
+
+    // Contract that receives the loan and executes the swaps; swap outputs are sent here
+    fn receiver(&self) -> Address;
+}
+
+// Stands in for Aave V3's flashLoanSimple until real calls are wired up
+#[derive(Debug)]
+pub struct AaveFlashLoanProvider {
+    pool: Address,
+    receiver: Address,
+    premium_bps: u32,
+    sequence: AtomicU64,
+}
+
+impl AaveFlashLoanProvider {
+    pub fn new(pool: Address, receiver: Address) -> Self {
+        // Aave V3 charges a 5 bps premium on flash loans
+        Self::with_premium(pool, receiver, 5)
+    }
+
+    pub fn with_premium(pool: Address, receiver: Address, premium_bps: u32) -> Self {
+        Self { pool, receiver, premium_bps, sequence: AtomicU64::new(0) }
+    }
+}
+
+#[async_trait]
+impl FlashLoanProvider for AaveFlashLoanProvider {
+    async fn flash_loan(&self, token: Address, amount: U256, callback_data: Vec<u8>) -> Result<String, ArbitrageError> {
+        tracing::info!(pool = ?self.pool, receiver = ?self.receiver, ?token, %amount, callback_len = callback_data.len(), "requesting flash loan");
+
+        let sequence = self.sequence.fetch_add(1, Ordering::SeqCst);
+        let preimage = [
+            token.as_bytes(),
+            &<[u8; 32]>::from(amount),
+            &callback_data,
+            &sequence.to_be_bytes(),
+        ].concat();
+
+        Ok(format!("0x{}", hex::encode(web3::signing::keccak256(&preimage))))
+    }
+
+    fn loan_fee(&self, amount: U256) -> U256 {
+        // Rounded up, as the pool does
+        let fee = amount.saturating_mul(U256::from(self.premium_bps));
+        (fee + U256::from(9_999)) / U256::from(10_000)
+    }
+
+    fn receiver(&self) -> Address {
+        self.receiver
+    }
+}
@@ -0,0 +1,228 @@
+// EIP-1559 gas price suggestions for CryptoQuest Arbitrage Bot
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::sync::RwLock;
+use tokio::time::{Duration, Instant};
+use web3::types::{Address, BlockNumber, Bytes, CallRequest, FeeHistory, U256};
+
+use crate::pools::encode_call;
+use crate::ArbitrageError;
+
+// Blocks of history sampled per suggestion, and the priority-fee percentile taken from each
+const FEE_HISTORY_BLOCKS: u64 = 10;
+const PRIORITY_FEE_PERCENTILE: f64 = 50.0;
+
+// OP-stack GasPriceOracle predeploy, which exposes the L1 base fee the sequencer charges against
+const GAS_PRICE_ORACLE: &str = "0x420000000000000000000000000000000000000F";
+
+// L1 calldata gas per serialized byte, and the bytes a signed transaction adds around its data
+const L1_ZERO_BYTE_GAS: u64 = 4;
+const L1_NONZERO_BYTE_GAS: u64 = 16;
+const L1_TX_OVERHEAD_BYTES: u64 = 68;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct GasSuggestion {
+    pub base_fee_per_gas: U256,
+    pub max_fee_per_gas: U256,
+    pub max_priority_fee_per_gas: U256,
+}
+
+impl GasSuggestion {
+    pub fn from_fee_history(history: &FeeHistory) -> Result<Self, ArbitrageError> {
+        // The last base fee is the one projected for the next block
+        let base_fee_per_gas = *history.base_fee_per_gas.last()
+            .ok_or_else(|| ArbitrageError::Rpc("fee history returned no base fees".to_string()))?;
+
+        let mut rewards: Vec<U256> = history.reward.iter()
+            .flatten()
+            .filter_map(|block| block.first().copied())
+            .collect();
+        rewards.sort();
+        let max_priority_fee_per_gas = rewards.get(rewards.len() / 2).copied().unwrap_or_default();
+
+        // Doubling the base fee keeps the transaction valid through several full blocks
+        let max_fee_per_gas = base_fee_per_gas.saturating_mul(U256::from(2)).saturating_add(max_priority_fee_per_gas);
+
+        Ok(Self { base_fee_per_gas, max_fee_per_gas, max_priority_fee_per_gas })
+    }
+}
+
+// Per-network suggestions reused for a short TTL, so every leg of a route doesn't
+// issue its own eth_feeHistory call
+#[derive(Debug)]
+pub struct GasOracle {
+    ttl: Duration,
+    suggestions: RwLock<HashMap<String, (Instant, GasSuggestion)>>,
+    l1_base_fees: RwLock<HashMap<String, (Instant, U256)>>,
+}
+
+impl GasOracle {
+    pub fn new(ttl: Duration) -> Self {
+        Self {
+            ttl,
+            suggestions: RwLock::new(HashMap::new()),
+            l1_base_fees: RwLock::new(HashMap::new()),
+        }
+    }
+
+    pub fn ttl(&self) -> Duration {
+        self.ttl
+    }
+
+    pub fn cached(&self, network: &str) -> Option<GasSuggestion> {
+        let suggestions = self.suggestions.read().unwrap();
+        let (fetched_at, suggestion) = suggestions.get(network)?;
+
+        (fetched_at.elapsed() < self.ttl).then_some(*suggestion)
+    }
+
+    pub async fn suggest(&self, network: &str, rpc_url: &str) -> Result<GasSuggestion, ArbitrageError> {
+        if let Some(suggestion) = self.cached(network) {
+            return Ok(suggestion);
+        }
+
+        let transport = web3::transports::Http::new(rpc_url)?;
+        let history = web3::Web3::new(transport).eth()
+            .fee_history(U256::from(FEE_HISTORY_BLOCKS), BlockNumber::Latest, Some(vec![PRIORITY_FEE_PERCENTILE]))
+            .await?;
+        let suggestion = GasSuggestion::from_fee_history(&history)?;
+
+        self.suggestions.write().unwrap().insert(network.to_string(), (Instant::now(), suggestion));
+        Ok(suggestion)
+    }
+
+    pub fn cached_l1_base_fee(&self, network: &str) -> Option<U256> {
+        let fees = self.l1_base_fees.read().unwrap();
+        let (fetched_at, fee) = fees.get(network)?;
+
+        (fetched_at.elapsed() < self.ttl).then_some(*fee)
+    }
+
+    pub async fn l1_base_fee(&self, network: &str, rpc_url: &str) -> Result<U256, ArbitrageError> {
+        // l1BaseFee() on the GasPriceOracle predeploy of an OP-stack network
+        if let Some(fee) = self.cached_l1_base_fee(network) {
+            return Ok(fee);
+        }
+
+        let call = CallRequest {
+            to: GAS_PRICE_ORACLE.parse::<Address>().ok(),
+            data: Some(Bytes(encode_call("l1BaseFee()", &[]))),
+            ..Default::default()
+        };
+        let transport = web3::transports::Http::new(rpc_url)?;
+        let output = web3::Web3::new(transport).eth().call(call, None).await?;
+        if output.0.len() < 32 {
+            return Err(ArbitrageError::Rpc(format!("GasPriceOracle on {} returned no l1BaseFee", network)));
+        }
+        let fee = U256::from_big_endian(&output.0[..32]);
+
+        self.l1_base_fees.write().unwrap().insert(network.to_string(), (Instant::now(), fee));
+        Ok(fee)
+    }
+}
+
+pub fn l1_data_gas(calldata: &[u8]) -> U256 {
+    // L1 gas to post a transaction with this calldata; the envelope and signature are
+    // counted as non-zero bytes
+    let zero_bytes = calldata.iter().filter(|byte| **byte == 0).count() as u64;
+    let nonzero_bytes = calldata.len() as u64 - zero_bytes + L1_TX_OVERHEAD_BYTES;
+
+    U256::from(zero_bytes * L1_ZERO_BYTE_GAS + nonzero_bytes * L1_NONZERO_BYTE_GAS)
+}
+
+impl Default for GasOracle {
+    fn default() -> Self {
+        Self::new(Duration::from_secs(12))
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Urgency {
+    Low,
+    #[default]
+    Normal,
+    // High-value, short-lived opportunities worth paying more for faster inclusion
+    High,
+}
+
+// Gas limits a transaction may carry; the floor is the intrinsic cost of a plain transfer
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct GasLimitBand {
+    pub min: u64,
+    pub max: u64,
+}
+
+impl GasLimitBand {
+    pub fn new(min: u64, max: u64) -> Self {
+        Self { min, max }
+    }
+
+    pub fn check(&self, gas_limit: u64) -> Result<(), ArbitrageError> {
+        if gas_limit < self.min {
+            return Err(ArbitrageError::GasLimitTooLow(gas_limit));
+        }
+        if gas_limit > self.max {
+            return Err(ArbitrageError::GasLimitTooHigh(gas_limit));
+        }
+        Ok(())
+    }
+}
+
+impl Default for GasLimitBand {
+    fn default() -> Self {
+        // A swap plus approval headroom, well short of what a revert could burn at block-level limits
+        Self { min: 21_000, max: 2_000_000 }
+    }
+}
+
+// Largest safety multiplier the builder accepts; anything above is a misconfiguration
+pub const MAX_GAS_MULTIPLIER: f64 = 10.0;
+
+// Safety multipliers on estimated gas, by urgency, with optional per-network overrides
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct GasMultipliers {
+    low: f64,
+    normal: f64,
+    high: f64,
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    networks: HashMap<String, HashMap<Urgency, f64>>,
+}
+
+impl GasMultipliers {
+    pub fn get(&self, network: &str, urgency: Urgency) -> f64 {
+        // A network override wins over the urgency's default
+        if let Some(multiplier) = self.networks.get(network).and_then(|overrides| overrides.get(&urgency)) {
+            return *multiplier;
+        }
+
+        match urgency {
+            Urgency::Low => self.low,
+            Urgency::Normal => self.normal,
+            Urgency::High => self.high,
+        }
+    }
+
+    pub fn set_default(&mut self, urgency: Urgency, multiplier: f64) {
+        match urgency {
+            Urgency::Low => self.low = multiplier,
+            Urgency::Normal => self.normal = multiplier,
+            Urgency::High => self.high = multiplier,
+        }
+    }
+
+    pub fn set_network(&mut self, network: impl Into<String>, urgency: Urgency, multiplier: f64) {
+        self.networks.entry(network.into()).or_default().insert(urgency, multiplier);
+    }
+
+    pub fn values(&self) -> impl Iterator<Item = f64> + '_ {
+        [self.low, self.normal, self.high].into_iter()
+            .chain(self.networks.values().flat_map(|overrides| overrides.values().copied()))
+    }
+}
+
+impl Default for GasMultipliers {
+    fn default() -> Self {
+        Self { low: 1.1, normal: 1.2, high: 1.5, networks: HashMap::new() }
+    }
+}
@@ -0,0 +1,47 @@
+// RPC health checks for CryptoQuest Arbitrage Bot
+use serde::Serialize;
+use tokio::time::{Duration, Instant};
+
+use crate::ArbitrageError;
+
+// Per-endpoint budget; a node slower than this is no use for trading anyway
+pub const HEALTH_CHECK_TIMEOUT: Duration = Duration::from_secs(3);
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(tag = "status", rename_all = "snake_case")]
+pub enum HealthStatus {
+    Ok { latency_ms: u64, block: u64 },
+    // The endpoint answers for a different chain than the network is configured for
+    ChainIdMismatch { expected: u64, actual: u64 },
+    Unreachable { reason: String },
+}
+
+impl HealthStatus {
+    pub fn is_ok(&self) -> bool {
+        matches!(self, HealthStatus::Ok { .. })
+    }
+}
+
+pub async fn check_rpc(rpc_url: &str, expected_chain_id: u64, timeout: Duration) -> HealthStatus {
+    // eth_chainId and eth_blockNumber together; latency covers both round trips
+    let started = Instant::now();
+    let probe = async {
+        let transport = web3::transports::Http::new(rpc_url)?;
+        let eth = web3::Web3::new(transport).eth();
+        let (chain_id, block) = futures::try_join!(eth.chain_id(), eth.block_number())?;
+        Ok::<_, ArbitrageError>((chain_id, block))
+    };
+
+    match tokio::time::timeout(timeout, probe).await {
+        Ok(Ok((chain_id, _))) if chain_id != expected_chain_id.into() => HealthStatus::ChainIdMismatch {
+            expected: expected_chain_id,
+            actual: chain_id.low_u64(),
+        },
+        Ok(Ok((_, block))) => HealthStatus::Ok {
+            latency_ms: started.elapsed().as_millis() as u64,
+            block: block.as_u64(),
+        },
+        Ok(Err(err)) => HealthStatus::Unreachable { reason: err.to_string() },
+        Err(_) => HealthStatus::Unreachable { reason: format!("no response within {:?}", timeout) },
+    }
+}
@@ -0,0 +1,116 @@
+// Trade journal backends for CryptoQuest Arbitrage Bot
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
+use std::sync::Mutex;
+use tokio::io::AsyncWriteExt;
+
+use crate::{ArbitrageError, ArbitrageOpportunity, ExecutionResult};
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct JournalEntry {
+    pub recorded_at: u64,
+    pub opportunity: ArbitrageOpportunity,
+    // Exactly one of result or error is set
+    pub result: Option<ExecutionResult>,
+    pub error: Option<String>,
+}
+
+impl JournalEntry {
+    pub fn new(opportunity: &ArbitrageOpportunity, result: &Result<ExecutionResult, ArbitrageError>) -> Self {
+        let (result, error) = match result {
+            Ok(result) => (Some(result.clone()), None),
+            Err(err) => (None, Some(err.to_string())),
+        };
+
+        Self {
+            recorded_at: crate::unix_now(),
+            opportunity: opportunity.clone(),
+            result,
+            error,
+        }
+    }
+}
+
+#[async_trait]
+pub trait TradeJournal: Send + Sync {
+    async fn record(&self, opportunity: &ArbitrageOpportunity, result: &Result<ExecutionResult, ArbitrageError>);
+
+    async fn load_recent(&self, limit: usize) -> Vec<JournalEntry>;
+}
+
+#[derive(Debug, Default)]
+pub struct InMemoryJournal {
+    entries: Mutex<Vec<JournalEntry>>,
+}
+
+impl InMemoryJournal {
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+#[async_trait]
+impl TradeJournal for InMemoryJournal {
+    async fn record(&self, opportunity: &ArbitrageOpportunity, result: &Result<ExecutionResult, ArbitrageError>) {
+        self.entries.lock().unwrap().push(JournalEntry::new(opportunity, result));
+    }
+
+    async fn load_recent(&self, limit: usize) -> Vec<JournalEntry> {
+        let entries = self.entries.lock().unwrap();
+        entries[entries.len().saturating_sub(limit)..].to_vec()
+    }
+}
+
+// Appends one JSON object per line, so the file can be tailed or loaded into analysis tools
+#[derive(Debug)]
+pub struct JsonLinesJournal {
+    path: PathBuf,
+    write_lock: tokio::sync::Mutex<()>,
+}
+
+impl JsonLinesJournal {
+    pub fn new(path: impl Into<PathBuf>) -> Self {
+        Self {
+            path: path.into(),
+            write_lock: tokio::sync::Mutex::new(()),
+        }
+    }
+
+    async fn append(&self, entry: &JournalEntry) -> std::io::Result<()> {
+        let mut line = serde_json::to_string(entry)?;
+        line.push('\n');
+
+        let _guard = self.write_lock.lock().await;
+        let mut file = tokio::fs::OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&self.path)
+            .await?;
+        file.write_all(line.as_bytes()).await
+    }
+}
+
+#[async_trait]
+impl TradeJournal for JsonLinesJournal {
+    async fn record(&self, opportunity: &ArbitrageOpportunity, result: &Result<ExecutionResult, ArbitrageError>) {
+        if let Err(err) = self.append(&JournalEntry::new(opportunity, result)).await {
+            tracing::warn!(path = %self.path.display(), error = %err, "failed to write journal entry");
+        }
+    }
+
+    async fn load_recent(&self, limit: usize) -> Vec<JournalEntry> {
+        let contents = match tokio::fs::read_to_string(&self.path).await {
+            Ok(contents) => contents,
+            Err(_) => return Vec::new(),
+        };
+
+        // Skip lines that fail to parse rather than discarding the whole journal
+        let entries: Vec<JournalEntry> = contents
+            .lines()
+            .filter_map(|line| serde_json::from_str(line).ok())
+            .collect();
+
+        entries[entries.len().saturating_sub(limit)..].to_vec()
+    }
+}
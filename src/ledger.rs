@@ -0,0 +1,227 @@
+// Ledger hardware wallet signing for CryptoQuest Arbitrage Bot
+use async_trait::async_trait;
+use std::fs::{self, File, OpenOptions};
+use std::io::{self, Read, Write};
+use std::path::{Path, PathBuf};
+use tokio::time::Duration;
+use web3::signing::Signature;
+use web3::types::{Address, H256};
+
+use crate::signer::encode_eip1559;
+use crate::{ArbitrageError, SecureTransaction, Signer};
+
+const LEDGER_VENDOR_ID: &str = "00002C97";
+
+// Ledger HID transport framing
+const HID_PACKET_SIZE: usize = 64;
+const HID_HEADER_SIZE: usize = 5;
+const HID_CHANNEL: u16 = 0x0101;
+const HID_TAG_APDU: u8 = 0x05;
+
+// Ethereum app commands and status words
+const CLA_ETH: u8 = 0xe0;
+const INS_GET_ADDRESS: u8 = 0x02;
+const INS_SIGN_TRANSACTION: u8 = 0x04;
+const APDU_CHUNK_SIZE: usize = 255;
+const SW_OK: u16 = 0x9000;
+const SW_USER_REJECTED: u16 = 0x6985;
+
+const HARDENED: u32 = 0x8000_0000;
+
+// Signs on a Ledger running the Ethereum app, so the key never leaves the device.
+// Every signature needs the operator to confirm the transaction on the device.
+#[derive(Debug, Clone)]
+pub struct LedgerSigner {
+    device: PathBuf,
+    derivation_path: Vec<u32>,
+    address: Address,
+    timeout: Duration,
+}
+
+impl LedgerSigner {
+    pub async fn connect(account_index: u32, timeout: Duration) -> Result<Self, ArbitrageError> {
+        // First Ledger found among the hidraw devices
+        Self::open(find_ledger()?, account_index, timeout).await
+    }
+
+    pub async fn open(device: impl Into<PathBuf>, account_index: u32, timeout: Duration) -> Result<Self, ArbitrageError> {
+        // Standard Ethereum derivation path m/44'/60'/0'/0/account_index
+        let device = device.into();
+        let derivation_path = vec![44 | HARDENED, 60 | HARDENED, HARDENED, 0, account_index];
+        let request = apdu(INS_GET_ADDRESS, 0x00, &encode_derivation_path(&derivation_path));
+        let response = exchange(&device, timeout, vec![request]).await?;
+        let address = parse_address(&response)?;
+
+        Ok(Self { device, derivation_path, address, timeout })
+    }
+
+    pub fn account_index(&self) -> u32 {
+        self.derivation_path.last().copied().unwrap_or_default()
+    }
+}
+
+#[async_trait]
+impl Signer for LedgerSigner {
+    fn address(&self) -> Address {
+        self.address
+    }
+
+    async fn sign(&self, tx: &SecureTransaction, chain_id: u64) -> Result<Vec<u8>, ArbitrageError> {
+        // The unsigned type-2 payload goes to the device in 255-byte APDUs after the derivation path
+        if tx.tx_type != 2 {
+            return Err(ArbitrageError::Signing("ledger signer only signs EIP-1559 transactions".to_string()));
+        }
+
+        let mut payload = encode_derivation_path(&self.derivation_path);
+        payload.extend(encode_eip1559(tx, chain_id, None));
+        let requests = payload.chunks(APDU_CHUNK_SIZE)
+            .enumerate()
+            .map(|(index, chunk)| apdu(INS_SIGN_TRANSACTION, if index == 0 { 0x00 } else { 0x80 }, chunk))
+            .collect();
+
+        let response = exchange(&self.device, self.timeout, requests).await?;
+        if response.len() < 65 {
+            return Err(ArbitrageError::Signing(format!("ledger returned a {}-byte signature", response.len())));
+        }
+
+        let signature = Signature {
+            v: u64::from(response[0]),
+            r: H256::from_slice(&response[1..33]),
+            s: H256::from_slice(&response[33..65]),
+        };
+        Ok(encode_eip1559(tx, chain_id, Some(&signature)))
+    }
+}
+
+fn find_ledger() -> Result<PathBuf, ArbitrageError> {
+    // Linux hidraw nodes; the APDU channel is the device's first USB interface
+    let entries = fs::read_dir("/sys/class/hidraw")
+        .map_err(|err| ArbitrageError::Signing(format!("cannot list hid devices: {}", err)))?;
+    let mut nodes: Vec<PathBuf> = entries.filter_map(Result::ok).map(|entry| entry.path()).collect();
+    nodes.sort();
+
+    nodes.into_iter()
+        .find(|node| {
+            let uevent = fs::read_to_string(node.join("device/uevent")).unwrap_or_default();
+            let interface = fs::read_to_string(node.join("device/../bInterfaceNumber")).unwrap_or_default();
+            uevent.lines().any(|line| line.starts_with("HID_ID=") && line.to_uppercase().contains(LEDGER_VENDOR_ID))
+                && interface.trim() == "00"
+        })
+        .and_then(|node| node.file_name().map(|name| Path::new("/dev").join(name)))
+        .ok_or_else(|| ArbitrageError::Signing("no Ledger device found".to_string()))
+}
+
+async fn exchange(device: &Path, timeout: Duration, requests: Vec<Vec<u8>>) -> Result<Vec<u8>, ArbitrageError> {
+    // HID reads block and can't be interrupted, so on timeout the task is left to finish
+    // whenever the device answers or is unplugged
+    let device = device.to_path_buf();
+    let io = tokio::task::spawn_blocking(move || {
+        let hid_error = |err: io::Error| ArbitrageError::Signing(format!("ledger {}: {}", device.display(), err));
+        let mut file = OpenOptions::new().read(true).write(true).open(&device).map_err(hid_error)?;
+
+        let mut response = Vec::new();
+        for request in requests {
+            response = transmit(&mut file, &request).map_err(hid_error)?;
+            check_status(&mut response)?;
+        }
+        Ok(response)
+    });
+
+    match tokio::time::timeout(timeout, io).await {
+        Ok(joined) => joined.map_err(|err| ArbitrageError::Signing(err.to_string()))?,
+        Err(_) => Err(ArbitrageError::Signing(format!("ledger did not respond within {:?}", timeout))),
+    }
+}
+
+fn transmit(device: &mut File, request: &[u8]) -> io::Result<Vec<u8>> {
+    // 64-byte reports tagged with the channel and a sequence number; the first one carries
+    // the total length. Writes are prefixed with the hidraw report id 0.
+    let mut framed = (request.len() as u16).to_be_bytes().to_vec();
+    framed.extend_from_slice(request);
+
+    for (sequence, chunk) in framed.chunks(HID_PACKET_SIZE - HID_HEADER_SIZE).enumerate() {
+        let mut report = [0u8; HID_PACKET_SIZE + 1];
+        report[1..3].copy_from_slice(&HID_CHANNEL.to_be_bytes());
+        report[3] = HID_TAG_APDU;
+        report[4..6].copy_from_slice(&(sequence as u16).to_be_bytes());
+        report[6..6 + chunk.len()].copy_from_slice(chunk);
+        device.write_all(&report)?;
+    }
+
+    let mut response = Vec::new();
+    let mut expected_len = None;
+    let mut sequence = 0u16;
+    loop {
+        let mut report = [0u8; HID_PACKET_SIZE];
+        let read = device.read(&mut report)?;
+        if read < HID_HEADER_SIZE
+            || report[0..2] != HID_CHANNEL.to_be_bytes()
+            || report[2] != HID_TAG_APDU
+            || report[3..5] != sequence.to_be_bytes()
+        {
+            return Err(io::Error::new(io::ErrorKind::InvalidData, "unexpected HID report"));
+        }
+
+        let mut data = &report[HID_HEADER_SIZE..read];
+        let len = match expected_len {
+            Some(len) => len,
+            None if data.len() >= 2 => {
+                let len = usize::from(u16::from_be_bytes([data[0], data[1]]));
+                data = &data[2..];
+                expected_len = Some(len);
+                len
+            }
+            None => return Err(io::Error::new(io::ErrorKind::InvalidData, "HID response without length")),
+        };
+
+        response.extend_from_slice(data);
+        if response.len() >= len {
+            response.truncate(len);
+            return Ok(response);
+        }
+        sequence += 1;
+    }
+}
+
+fn check_status(response: &mut Vec<u8>) -> Result<(), ArbitrageError> {
+    // Strips the trailing status word, mapping a declined prompt to UserRejected
+    if response.len() < 2 {
+        return Err(ArbitrageError::Signing("ledger response without status word".to_string()));
+    }
+
+    let status = u16::from_be_bytes([response[response.len() - 2], response[response.len() - 1]]);
+    response.truncate(response.len() - 2);
+
+    match status {
+        SW_OK => Ok(()),
+        SW_USER_REJECTED => Err(ArbitrageError::UserRejected),
+        other => Err(ArbitrageError::Signing(format!("ledger returned status {:#06x}", other))),
+    }
+}
+
+fn apdu(instruction: u8, p1: u8, data: &[u8]) -> Vec<u8> {
+    let mut request = vec![CLA_ETH, instruction, p1, 0x00, data.len() as u8];
+    request.extend_from_slice(data);
+    request
+}
+
+fn encode_derivation_path(path: &[u32]) -> Vec<u8> {
+    let mut encoded = vec![path.len() as u8];
+    for index in path {
+        encoded.extend_from_slice(&index.to_be_bytes());
+    }
+    encoded
+}
+
+fn parse_address(response: &[u8]) -> Result<Address, ArbitrageError> {
+    // Public key length and bytes, then the address as hex text
+    let invalid = || ArbitrageError::Signing("ledger returned a malformed address response".to_string());
+    let public_key_len = usize::from(*response.first().ok_or_else(invalid)?);
+    let address_len = usize::from(*response.get(1 + public_key_len).ok_or_else(invalid)?);
+    let address = response.get(2 + public_key_len..2 + public_key_len + address_len).ok_or_else(invalid)?;
+
+    std::str::from_utf8(address)
+        .ok()
+        .and_then(|hex| hex.trim_start_matches("0x").parse::<Address>().ok())
+        .ok_or_else(invalid)
+}
@@ -0,0 +1,108 @@
+// Execution metrics for CryptoQuest Arbitrage Bot
+use std::collections::BTreeMap;
+use std::fmt::Write;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Mutex;
+
+// Upper bounds of the realized net profit histogram buckets, in profit token units
+const PROFIT_BUCKETS: [f64; 7] = [0.0, 1.0, 10.0, 100.0, 1_000.0, 10_000.0, 100_000.0];
+
+#[derive(Debug, Default)]
+pub struct Metrics {
+    opportunities_validated: AtomicU64,
+    opportunities_rejected: Mutex<BTreeMap<String, u64>>,
+    arbitrages_executed: AtomicU64,
+    bridge_timeouts: AtomicU64,
+    net_profit: Mutex<ProfitHistogram>,
+}
+
+#[derive(Debug, Default)]
+struct ProfitHistogram {
+    buckets: [u64; PROFIT_BUCKETS.len()],
+    sum: f64,
+    count: u64,
+}
+
+impl Metrics {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn record_validated(&self) {
+        self.opportunities_validated.fetch_add(1, Ordering::Relaxed);
+    }
+
+    pub fn record_rejection(&self, reason: &str) {
+        *self.opportunities_rejected.lock().unwrap().entry(reason.to_string()).or_default() += 1;
+    }
+
+    pub fn record_bridge_timeout(&self) {
+        self.bridge_timeouts.fetch_add(1, Ordering::Relaxed);
+    }
+
+    pub fn record_executed(&self, net_profit: f64) {
+        self.arbitrages_executed.fetch_add(1, Ordering::Relaxed);
+
+        let mut histogram = self.net_profit.lock().unwrap();
+        for (bucket, bound) in histogram.buckets.iter_mut().zip(PROFIT_BUCKETS) {
+            if net_profit <= bound {
+                *bucket += 1;
+            }
+        }
+        histogram.sum += net_profit;
+        histogram.count += 1;
+    }
+
+    pub fn opportunities_validated(&self) -> u64 {
+        self.opportunities_validated.load(Ordering::Relaxed)
+    }
+
+    pub fn opportunities_rejected(&self) -> BTreeMap<String, u64> {
+        self.opportunities_rejected.lock().unwrap().clone()
+    }
+
+    pub fn arbitrages_executed(&self) -> u64 {
+        self.arbitrages_executed.load(Ordering::Relaxed)
+    }
+
+    pub fn bridge_timeouts(&self) -> u64 {
+        self.bridge_timeouts.load(Ordering::Relaxed)
+    }
+
+    pub fn encode_prometheus(&self) -> String {
+        // Prometheus text exposition format, suitable for serving from /metrics
+        let mut out = String::new();
+
+        write_counter(&mut out, "arbitrage_opportunities_validated_total",
+            "Opportunities that passed validation", self.opportunities_validated());
+
+        out.push_str("# HELP arbitrage_opportunities_rejected_total Opportunities rejected during validation, by reason\n");
+        out.push_str("# TYPE arbitrage_opportunities_rejected_total counter\n");
+        for (reason, count) in self.opportunities_rejected() {
+            let _ = writeln!(out, "arbitrage_opportunities_rejected_total{{reason=\"{}\"}} {}", reason, count);
+        }
+
+        write_counter(&mut out, "arbitrage_executions_total",
+            "Cross-chain arbitrages executed successfully", self.arbitrages_executed());
+        write_counter(&mut out, "arbitrage_bridge_timeouts_total",
+            "Bridge transfers that were not confirmed in time", self.bridge_timeouts());
+
+        let histogram = self.net_profit.lock().unwrap();
+        out.push_str("# HELP arbitrage_realized_net_profit Net profit of executed arbitrages\n");
+        out.push_str("# TYPE arbitrage_realized_net_profit histogram\n");
+        for (count, bound) in histogram.buckets.iter().zip(PROFIT_BUCKETS) {
+            let _ = writeln!(out, "arbitrage_realized_net_profit_bucket{{le=\"{}\"}} {}", bound, count);
+        }
+        let _ = writeln!(out, "arbitrage_realized_net_profit_bucket{{le=\"+Inf\"}} {}", histogram.count);
+        let _ = writeln!(out, "arbitrage_realized_net_profit_sum {}", histogram.sum);
+        let _ = writeln!(out, "arbitrage_realized_net_profit_count {}", histogram.count);
+
+        out
+    }
+}
+
+fn write_counter(out: &mut String, name: &str, help: &str, value: u64) {
+    let _ = writeln!(out, "# HELP {} {}", name, help);
+    let _ = writeln!(out, "# TYPE {} counter", name);
+    let _ = writeln!(out, "{} {}", name, value);
+}
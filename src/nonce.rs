@@ -0,0 +1,67 @@
+// Per-network nonce tracking for CryptoQuest Arbitrage Bot
+use std::collections::HashMap;
+use std::sync::RwLock;
+use tokio::sync::Mutex;
+use web3::types::{Address, BlockNumber, U256};
+
+use crate::ArbitrageError;
+
+// Hands out nonces per (network, sender) so concurrent executions never reuse one.
+// The chain's pending count is only read on first use or when reconciling.
+#[derive(Debug, Default)]
+pub struct NonceManager {
+    endpoints: RwLock<HashMap<String, String>>,
+    next: Mutex<HashMap<(String, Address), U256>>,
+}
+
+impl NonceManager {
+    pub fn new(endpoints: HashMap<String, String>) -> Self {
+        Self {
+            endpoints: RwLock::new(endpoints),
+            next: Mutex::new(HashMap::new()),
+        }
+    }
+
+    pub fn set_endpoint(&self, network: &str, rpc_url: &str) {
+        self.endpoints.write().unwrap().insert(network.to_string(), rpc_url.to_string());
+    }
+
+    pub async fn next_nonce(&self, network: &str, address: Address) -> Result<U256, ArbitrageError> {
+        // Lock held across the fetch so two first-time callers can't both read the same count
+        let mut next = self.next.lock().await;
+        let key = (network.to_string(), address);
+
+        let nonce = match next.get(&key) {
+            Some(nonce) => *nonce,
+            None => self.pending_count(network, address).await?,
+        };
+
+        next.insert(key, nonce + 1);
+        Ok(nonce)
+    }
+
+    pub async fn reconcile(&self, network: &str, address: Address) -> Result<U256, ArbitrageError> {
+        // Resync with the chain, e.g. after a dropped or replaced transaction
+        let mut next = self.next.lock().await;
+        let pending = self.pending_count(network, address).await?;
+
+        next.insert((network.to_string(), address), pending);
+        Ok(pending)
+    }
+
+    pub async fn set_nonce(&self, network: &str, address: Address, nonce: U256) {
+        self.next.lock().await.insert((network.to_string(), address), nonce);
+    }
+
+    async fn pending_count(&self, network: &str, address: Address) -> Result<U256, ArbitrageError> {
+        let rpc_url = self.endpoints.read().unwrap().get(network).cloned()
+            .ok_or_else(|| ArbitrageError::UnknownNetwork(network.to_string()))?;
+
+        let transport = web3::transports::Http::new(&rpc_url)?;
+        let count = web3::Web3::new(transport).eth()
+            .transaction_count(address, Some(BlockNumber::Pending))
+            .await?;
+
+        Ok(count)
+    }
+}
@@ -0,0 +1,36 @@
+// Execution stage callbacks for CryptoQuest Arbitrage Bot
+use serde::{Deserialize, Serialize};
+
+use crate::{ArbitrageError, ArbitrageOpportunity, ExecutionResult};
+
+// On-chain step a confirmation belongs to
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ExecutionStage {
+    Source,
+    Bridge,
+    Target,
+}
+
+// Notified as execute_cross_chain_arbitrage moves through its stages. Calls are made inline
+// on the executing task, so implementations should hand work off rather than block. Every
+// method defaults to doing nothing.
+pub trait ExecutionObserver: Send + Sync {
+    // Passed the security checks and is about to be costed and executed
+    fn on_validated(&self, _opportunity: &ArbitrageOpportunity) {}
+
+    fn on_source_submitted(&self, _opportunity: &ArbitrageOpportunity, _tx_hash: &str) {}
+
+    fn on_bridge_submitted(&self, _opportunity: &ArbitrageOpportunity, _tx_hash: &str) {}
+
+    // A submitted step landed successfully
+    fn on_confirmed(&self, _opportunity: &ArbitrageOpportunity, _stage: ExecutionStage, _tx_hash: &str) {}
+
+    fn on_target_submitted(&self, _opportunity: &ArbitrageOpportunity, _tx_hash: &str) {}
+
+    // One of these ends every attempt; calls refused by the kill switch or circuit breaker, or
+    // answered from the execution ledger, aren't attempts and notify nothing
+    fn on_completed(&self, _opportunity: &ArbitrageOpportunity, _result: &ExecutionResult) {}
+
+    fn on_failed(&self, _opportunity: &ArbitrageOpportunity, _error: &ArbitrageError) {}
+}
@@ -0,0 +1,104 @@
+// EIP-2612 permits for CryptoQuest Arbitrage Bot
+use ethabi::Token;
+use serde::{Deserialize, Serialize};
+use web3::signing::{keccak256, Signature};
+use web3::types::{Address, Bytes, CallRequest, H256, U256};
+
+use crate::pools::encode_call;
+use crate::ArbitrageError;
+
+// Permit(address owner,address spender,uint256 value,uint256 nonce,uint256 deadline)
+fn permit_typehash() -> H256 {
+    H256(keccak256(b"Permit(address owner,address spender,uint256 value,uint256 nonce,uint256 deadline)"))
+}
+
+// Everything an owner signs to grant spender an allowance of value without an approve
+// transaction. The domain separator is read from the token, so it already covers its name,
+// version, chain id and address.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PermitData {
+    pub token: Address,
+    pub owner: Address,
+    pub spender: Address,
+    pub value: U256,
+    pub nonce: U256,
+    pub deadline: u64,
+    pub domain_separator: H256,
+}
+
+impl PermitData {
+    pub fn struct_hash(&self) -> H256 {
+        H256(keccak256(&ethabi::encode(&[
+            Token::FixedBytes(permit_typehash().as_bytes().to_vec()),
+            Token::Address(self.owner),
+            Token::Address(self.spender),
+            Token::Uint(self.value),
+            Token::Uint(self.nonce),
+            Token::Uint(self.deadline.into()),
+        ])))
+    }
+
+    pub fn digest(&self) -> H256 {
+        // EIP-712: keccak256("\x19\x01" || domainSeparator || structHash), what gets signed
+        let mut preimage = Vec::with_capacity(66);
+        preimage.extend_from_slice(b"\x19\x01");
+        preimage.extend_from_slice(self.domain_separator.as_bytes());
+        preimage.extend_from_slice(self.struct_hash().as_bytes());
+        H256(keccak256(&preimage))
+    }
+}
+
+// A permit with the owner's signature, waiting to be bundled into a swap
+pub(crate) struct SignedPermit {
+    pub permit: PermitData,
+    pub signature: Signature,
+}
+
+pub(crate) async fn read_permit_domain(rpc_url: &str, token: Address, owner: Address) -> Result<(U256, H256), ArbitrageError> {
+    // nonces(owner) and DOMAIN_SEPARATOR(); tokens without EIP-2612 revert or return nothing
+    let transport = web3::transports::Http::new(rpc_url)?;
+    let eth = web3::Web3::new(transport).eth();
+    let call = |data: Vec<u8>| CallRequest { to: Some(token), data: Some(Bytes(data)), ..Default::default() };
+
+    let (nonce, separator) = futures::try_join!(
+        eth.call(call(encode_call("nonces(address)", &[Token::Address(owner)])), None),
+        eth.call(call(encode_call("DOMAIN_SEPARATOR()", &[])), None),
+    )?;
+    if nonce.0.len() < 32 || separator.0.len() < 32 {
+        return Err(ArbitrageError::Rpc(format!("{:?} does not support EIP-2612 permits", token)));
+    }
+
+    Ok((U256::from_big_endian(&nonce.0[..32]), H256::from_slice(&separator.0[..32])))
+}
+
+pub(crate) fn self_permit_calldata(SignedPermit { permit, signature }: &SignedPermit) -> Vec<u8> {
+    // SelfPermit on the router: it calls permit(msg.sender, router, ...) on the token itself
+    encode_call("selfPermit(address,uint256,uint256,uint8,bytes32,bytes32)", &[
+        Token::Address(permit.token),
+        Token::Uint(permit.value),
+        Token::Uint(permit.deadline.into()),
+        Token::Uint(signature.v.into()),
+        Token::FixedBytes(signature.r.as_bytes().to_vec()),
+        Token::FixedBytes(signature.s.as_bytes().to_vec()),
+    ])
+}
+
+pub(crate) fn multicall_calldata(calls: Vec<Vec<u8>>) -> Vec<u8> {
+    encode_call("multicall(bytes[])", &[Token::Array(calls.into_iter().map(Token::Bytes).collect())])
+}
+
+pub(crate) fn last_call_output(calldata: &[u8], output: Vec<u8>) -> Vec<u8> {
+    // multicall(bytes[]) returns every call's output, so a bundled swap's is the last one.
+    // Output of any other call is passed through.
+    if calldata.get(..4) != Some(&encode_call("multicall(bytes[])", &[])[..]) {
+        return output;
+    }
+
+    ethabi::decode(&[ethabi::ParamType::Array(Box::new(ethabi::ParamType::Bytes))], &output)
+        .ok()
+        .and_then(|mut tokens| tokens.pop())
+        .and_then(Token::into_array)
+        .and_then(|results| results.into_iter().last())
+        .and_then(Token::into_bytes)
+        .unwrap_or(output)
+}
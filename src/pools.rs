@@ -0,0 +1,263 @@
+// Pool state fetching and caching for CryptoQuest Arbitrage Bot
+use async_trait::async_trait;
+use ethabi::{ParamType, Token};
+use std::collections::HashMap;
+use std::sync::RwLock;
+use tokio::time::{Duration, Instant};
+use web3::types::{Address, Bytes, CallRequest, U256};
+
+use crate::events::sqrt_price_to_price;
+use crate::{default_decimals, ArbitrageError, PoolInfo, PoolKind, RateLimiter};
+
+// V2 pairs don't expose their fee; the canonical Uniswap V2 fee is 0.3%
+pub const V2_DEFAULT_FEE_TIER: u32 = 3000;
+
+// Curve fees are scaled by 1e10, fee tiers by 1e6
+const CURVE_FEE_SCALE: u64 = 10_000;
+
+// Balancer fees and normalized weights are scaled by 1e18
+const BALANCER_FEE_SCALE: u64 = 1_000_000_000_000;
+const BALANCER_WEIGHT_SCALE: f64 = 1e18;
+
+#[async_trait]
+pub trait PoolFetcher: Send + Sync {
+    async fn fetch_pool(&self, network: &str, address: &str) -> Result<PoolInfo, ArbitrageError>;
+}
+
+// Short-lived pool state keyed by network and address, so tight scan loops
+// don't query the same pool over and over
+#[derive(Debug)]
+pub struct PoolCache {
+    ttl: Duration,
+    entries: RwLock<HashMap<String, (Instant, PoolInfo)>>,
+}
+
+impl PoolCache {
+    pub fn new(ttl: Duration) -> Self {
+        Self {
+            ttl,
+            entries: RwLock::new(HashMap::new()),
+        }
+    }
+
+    pub fn key(network: &str, address: &str) -> String {
+        // Addresses compare case-insensitively, checksummed or not
+        format!("{}:{}", network, address.to_ascii_lowercase())
+    }
+
+    pub fn ttl(&self) -> Duration {
+        self.ttl
+    }
+
+    pub fn get(&self, key: &str) -> Option<PoolInfo> {
+        let entries = self.entries.read().unwrap();
+        let (inserted_at, pool) = entries.get(key)?;
+
+        (inserted_at.elapsed() < self.ttl).then(|| pool.clone())
+    }
+
+    pub fn insert(&self, key: impl Into<String>, pool: PoolInfo) {
+        self.entries.write().unwrap().insert(key.into(), (Instant::now(), pool));
+    }
+
+    pub fn clear_expired(&self) -> usize {
+        // Returns how many entries were dropped
+        let mut entries = self.entries.write().unwrap();
+        let before = entries.len();
+        entries.retain(|_, (inserted_at, _)| inserted_at.elapsed() < self.ttl);
+
+        before - entries.len()
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.read().unwrap().len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+impl Default for PoolCache {
+    fn default() -> Self {
+        Self::new(Duration::from_secs(2))
+    }
+}
+
+pub(crate) async fn read_pool_info(
+    rpc_url: &str,
+    network: &str,
+    address: Address,
+    kind: PoolKind,
+    rate_limiter: &RateLimiter
+) -> Result<PoolInfo, ArbitrageError> {
+    // Pool state straight from the contract: getReserves() for V2, slot0() and liquidity()
+    // for V3, balances and A() for Curve, weights plus the Vault's balances for Balancer.
+    // Amounts keep the token's raw units.
+    let transport = web3::transports::Http::new(rpc_url)?;
+    let web3 = web3::Web3::new(transport);
+    let call_at = |to: Address, signature: &'static str, params: Vec<Token>, outputs: Vec<ParamType>| {
+        let web3 = web3.clone();
+        async move {
+            rate_limiter.acquire(network).await;
+            let request = CallRequest {
+                to: Some(to),
+                data: Some(Bytes(encode_call(signature, &params))),
+                ..Default::default()
+            };
+            let output = web3.eth().call(request, None).await?;
+            ethabi::decode(&outputs, &output.0)
+                .map_err(|err| ArbitrageError::Rpc(format!("{} on {:?}: {}", signature, to, err)))
+        }
+    };
+    let call = |signature: &'static str, params: Vec<Token>, outputs: Vec<ParamType>| call_at(address, signature, params, outputs);
+    let coin = |index: u64| call("coins(uint256)", vec![Token::Uint(index.into())], vec![ParamType::Address]);
+
+    let mut pool = PoolInfo {
+        address: format!("{:?}", address),
+        network: network.to_string(),
+        token0: String::new(),
+        token1: String::new(),
+        price: 0.0,
+        liquidity: 0,
+        fee_tier: V2_DEFAULT_FEE_TIER,
+        reserve0: None,
+        reserve1: None,
+        decimals: default_decimals(),
+        kind,
+        dex: String::new(),
+    };
+
+    // Balancer pools hold no tokens themselves; the Vault reports their tokens and balances
+    if let PoolKind::Weighted { .. } = kind {
+        let pool_id = call("getPoolId()", Vec::new(), vec![ParamType::FixedBytes(32)]).await?;
+        let vault = call("getVault()", Vec::new(), vec![ParamType::Address]).await?;
+        let weights = call("getNormalizedWeights()", Vec::new(), vec![ParamType::Array(Box::new(ParamType::Uint(256)))]).await?;
+        let fee = call("getSwapFeePercentage()", Vec::new(), vec![ParamType::Uint(256)]).await?;
+        // getPoolTokens(bytes32) -> (address[] tokens, uint256[] balances, uint256 lastChangeBlock)
+        let pool_tokens = call_at(
+            address_at(&vault, 0)?,
+            "getPoolTokens(bytes32)",
+            pool_id,
+            vec![
+                ParamType::Array(Box::new(ParamType::Address)),
+                ParamType::Array(Box::new(ParamType::Uint(256))),
+                ParamType::Uint(256),
+            ]
+        ).await?;
+
+        let unexpected = || ArbitrageError::Rpc(format!("pool {:?} is not a two-token weighted pool", address));
+        let tokens = pool_tokens[0].clone().into_array().filter(|tokens| tokens.len() == 2).ok_or_else(unexpected)?;
+        let balances = pool_tokens[1].clone().into_array().filter(|balances| balances.len() == 2).ok_or_else(unexpected)?;
+        let weights = weights[0].clone().into_array().filter(|weights| weights.len() == 2).ok_or_else(unexpected)?;
+        let (reserve0, reserve1) = (uint_at(&balances, 0)?.low_u128(), uint_at(&balances, 1)?.low_u128());
+        let weight0 = uint_at(&weights, 0)?.low_u128() as f64 / BALANCER_WEIGHT_SCALE;
+        let weight1 = uint_at(&weights, 1)?.low_u128() as f64 / BALANCER_WEIGHT_SCALE;
+        if reserve0 == 0 || reserve1 == 0 {
+            return Err(ArbitrageError::ValidationFailed(format!("pool {:?} has no reserves", address)));
+        }
+
+        pool.token0 = format!("{:?}", address_at(&tokens, 0)?);
+        pool.token1 = format!("{:?}", address_at(&tokens, 1)?);
+        pool.kind = PoolKind::Weighted { weight0, weight1 };
+        pool.price = crate::weighted::spot_price(reserve0 as f64, weight0, reserve1 as f64, weight1);
+        pool.liquidity = (reserve0 as f64 * reserve1 as f64).sqrt() as u128;
+        pool.fee_tier = (uint_at(&fee, 0)?.low_u128() / BALANCER_FEE_SCALE as u128) as u32;
+        pool.reserve0 = Some(reserve0);
+        pool.reserve1 = Some(reserve1);
+
+        return Ok(pool);
+    }
+
+    let (token0, token1) = match kind {
+        PoolKind::StableSwap { .. } => (coin(0).await?, coin(1).await?),
+        _ => (
+            call("token0()", Vec::new(), vec![ParamType::Address]).await?,
+            call("token1()", Vec::new(), vec![ParamType::Address]).await?,
+        ),
+    };
+    pool.token0 = format!("{:?}", address_at(&token0, 0)?);
+    pool.token1 = format!("{:?}", address_at(&token1, 0)?);
+
+    match kind {
+        PoolKind::ConstantProduct => {
+            // getReserves() -> (uint112 reserve0, uint112 reserve1, uint32 blockTimestampLast)
+            let reserves = call(
+                "getReserves()",
+                Vec::new(),
+                vec![ParamType::Uint(112), ParamType::Uint(112), ParamType::Uint(32)]
+            ).await?;
+            let (reserve0, reserve1) = (uint_at(&reserves, 0)?.low_u128(), uint_at(&reserves, 1)?.low_u128());
+            if reserve0 == 0 {
+                return Err(ArbitrageError::ValidationFailed(format!("pool {:?} has no reserves", address)));
+            }
+
+            // Same derivation as a Sync log
+            pool.price = reserve1 as f64 / reserve0 as f64;
+            pool.liquidity = (reserve0 as f64 * reserve1 as f64).sqrt() as u128;
+            pool.reserve0 = Some(reserve0);
+            pool.reserve1 = Some(reserve1);
+        }
+        PoolKind::ConcentratedV3 => {
+            // slot0() -> (uint160 sqrtPriceX96, int24 tick, uint16 observationIndex,
+            //   uint16 observationCardinality, uint16 observationCardinalityNext,
+            //   uint8 feeProtocol, bool unlocked)
+            let slot0 = call("slot0()", Vec::new(), vec![
+                ParamType::Uint(160),
+                ParamType::Int(24),
+                ParamType::Uint(16),
+                ParamType::Uint(16),
+                ParamType::Uint(16),
+                ParamType::Uint(8),
+                ParamType::Bool,
+            ]).await?;
+            let liquidity = call("liquidity()", Vec::new(), vec![ParamType::Uint(128)]).await?;
+            let fee = call("fee()", Vec::new(), vec![ParamType::Uint(24)]).await?;
+
+            pool.price = sqrt_price_to_price(uint_at(&slot0, 0)?);
+            pool.liquidity = uint_at(&liquidity, 0)?.low_u128();
+            pool.fee_tier = uint_at(&fee, 0)?.low_u32();
+        }
+        PoolKind::StableSwap { amp } => {
+            // The configured amp is only a fallback; the pool's live A() wins
+            let balance = |index: u64| call("balances(uint256)", vec![Token::Uint(index.into())], vec![ParamType::Uint(256)]);
+            let reserve0 = uint_at(&balance(0).await?, 0)?.low_u128();
+            let reserve1 = uint_at(&balance(1).await?, 0)?.low_u128();
+            let live_amp = call("A()", Vec::new(), vec![ParamType::Uint(256)]).await
+                .and_then(|a| uint_at(&a, 0))
+                .map(|a| a.low_u64())
+                .unwrap_or(amp);
+            let fee = call("fee()", Vec::new(), vec![ParamType::Uint(256)]).await?;
+            if reserve0 == 0 || reserve1 == 0 {
+                return Err(ArbitrageError::ValidationFailed(format!("pool {:?} has no reserves", address)));
+            }
+
+            pool.kind = PoolKind::StableSwap { amp: live_amp };
+            pool.price = crate::stable_swap::spot_price(reserve0 as f64, reserve1 as f64, live_amp);
+            pool.liquidity = (reserve0 as f64 * reserve1 as f64).sqrt() as u128;
+            pool.fee_tier = (uint_at(&fee, 0)?.low_u64() / CURVE_FEE_SCALE) as u32;
+            pool.reserve0 = Some(reserve0);
+            pool.reserve1 = Some(reserve1);
+        }
+        // Already read from the Vault above
+        PoolKind::Weighted { .. } => {}
+    }
+
+    Ok(pool)
+}
+
+pub(crate) fn encode_call(signature: &str, params: &[Token]) -> Vec<u8> {
+    // Four-byte selector followed by the ABI-encoded arguments
+    let selector = &web3::signing::keccak256(signature.as_bytes())[..4];
+    [selector, &ethabi::encode(params)[..]].concat()
+}
+
+fn address_at(tokens: &[Token], index: usize) -> Result<Address, ArbitrageError> {
+    tokens.get(index).cloned().and_then(Token::into_address)
+        .ok_or_else(|| ArbitrageError::Rpc(format!("expected an address at output {}", index)))
+}
+
+fn uint_at(tokens: &[Token], index: usize) -> Result<U256, ArbitrageError> {
+    tokens.get(index).cloned().and_then(Token::into_uint)
+        .ok_or_else(|| ArbitrageError::Rpc(format!("expected an integer at output {}", index)))
+}
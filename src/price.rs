@@ -0,0 +1,153 @@
+// Token price feeds for CryptoQuest Arbitrage Bot
+use async_trait::async_trait;
+use ethabi::ParamType;
+use std::collections::HashMap;
+use std::sync::RwLock;
+use std::time::{SystemTime, UNIX_EPOCH};
+use tokio::time::{Duration, Instant};
+use web3::types::{Address, Bytes, CallRequest};
+
+use crate::pools::encode_call;
+use crate::ArbitrageError;
+
+#[async_trait]
+pub trait PriceOracle: Send + Sync {
+    // USD price of one whole token
+    async fn price_usd(&self, network: &str, token: Address) -> Result<f64, ArbitrageError>;
+}
+
+// Reads Chainlink USD aggregators, one feed per (network, token)
+#[derive(Debug)]
+pub struct ChainlinkPriceOracle {
+    endpoints: HashMap<String, String>,
+    feeds: HashMap<(String, Address), Address>,
+    max_staleness: Duration,
+}
+
+impl ChainlinkPriceOracle {
+    pub fn new(endpoints: HashMap<String, String>) -> Self {
+        // Chainlink USD feeds heartbeat at most hourly, so older answers are suspect
+        Self { endpoints, feeds: HashMap::new(), max_staleness: Duration::from_secs(3600) }
+    }
+
+    pub fn with_feed(mut self, network: impl Into<String>, token: Address, aggregator: Address) -> Self {
+        self.feeds.insert((network.into(), token), aggregator);
+        self
+    }
+
+    pub fn with_max_staleness(mut self, max_staleness: Duration) -> Self {
+        self.max_staleness = max_staleness;
+        self
+    }
+}
+
+#[async_trait]
+impl PriceOracle for ChainlinkPriceOracle {
+    async fn price_usd(&self, network: &str, token: Address) -> Result<f64, ArbitrageError> {
+        // latestRoundData() answer scaled by the aggregator's decimals()
+        let aggregator = *self.feeds.get(&(network.to_string(), token))
+            .ok_or_else(|| ArbitrageError::InvalidConfig(format!("no price feed for {:?} on {}", token, network)))?;
+        let rpc_url = self.endpoints.get(network)
+            .ok_or_else(|| ArbitrageError::UnknownNetwork(network.to_string()))?;
+        let eth = web3::Web3::new(web3::transports::Http::new(rpc_url)?).eth();
+        let call = |signature: &str| CallRequest {
+            to: Some(aggregator),
+            data: Some(Bytes(encode_call(signature, &[]))),
+            ..Default::default()
+        };
+        let feed_error = |err: ethabi::Error| ArbitrageError::Rpc(format!("price feed {:?}: {}", aggregator, err));
+
+        // (uint80 roundId, int256 answer, uint256 startedAt, uint256 updatedAt, uint80 answeredInRound)
+        let round = eth.call(call("latestRoundData()"), None).await?;
+        let round = ethabi::decode(&[
+            ParamType::Uint(80),
+            ParamType::Int(256),
+            ParamType::Uint(256),
+            ParamType::Uint(256),
+            ParamType::Uint(80),
+        ], &round.0).map_err(feed_error)?;
+        let decimals = eth.call(call("decimals()"), None).await?;
+        let decimals = ethabi::decode(&[ParamType::Uint(8)], &decimals.0).map_err(feed_error)?;
+
+        let answer = round[1].clone().into_int().unwrap_or_default();
+        let updated_at = round[3].clone().into_uint().unwrap_or_default().low_u64();
+        let decimals = decimals[0].clone().into_uint().unwrap_or_default().low_u32();
+
+        // int256 answers with the top bit set are negative, which no USD price can be
+        if answer.is_zero() || answer.bit(255) {
+            return Err(ArbitrageError::Rpc(format!("price feed {:?} returned a non-positive answer", aggregator)));
+        }
+        let now = SystemTime::now().duration_since(UNIX_EPOCH).unwrap_or_default().as_secs();
+        if now.saturating_sub(updated_at) > self.max_staleness.as_secs() {
+            return Err(ArbitrageError::Rpc(format!(
+                "price feed {:?} last updated {}s ago",
+                aggregator, now.saturating_sub(updated_at)
+            )));
+        }
+
+        Ok(answer.low_u128() as f64 / 10f64.powi(decimals as i32))
+    }
+}
+
+// Fixed prices, for tests and dry runs
+#[derive(Debug, Default)]
+pub struct StaticPriceOracle {
+    prices: RwLock<HashMap<(String, Address), f64>>,
+}
+
+impl StaticPriceOracle {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_price(self, network: impl Into<String>, token: Address, price_usd: f64) -> Self {
+        self.set_price(network, token, price_usd);
+        self
+    }
+
+    pub fn set_price(&self, network: impl Into<String>, token: Address, price_usd: f64) {
+        self.prices.write().unwrap().insert((network.into(), token), price_usd);
+    }
+}
+
+#[async_trait]
+impl PriceOracle for StaticPriceOracle {
+    async fn price_usd(&self, network: &str, token: Address) -> Result<f64, ArbitrageError> {
+        self.prices.read().unwrap().get(&(network.to_string(), token)).copied()
+            .ok_or_else(|| ArbitrageError::InvalidConfig(format!("no static price for {:?} on {}", token, network)))
+    }
+}
+
+// Prices reused for a short TTL, so a scan doesn't query the oracle once per opportunity
+#[derive(Debug)]
+pub struct PriceCache {
+    ttl: Duration,
+    prices: RwLock<HashMap<(String, Address), (Instant, f64)>>,
+}
+
+impl PriceCache {
+    pub fn new(ttl: Duration) -> Self {
+        Self { ttl, prices: RwLock::new(HashMap::new()) }
+    }
+
+    pub fn ttl(&self) -> Duration {
+        self.ttl
+    }
+
+    pub fn get(&self, network: &str, token: Address) -> Option<f64> {
+        let prices = self.prices.read().unwrap();
+        let (fetched_at, price) = prices.get(&(network.to_string(), token))?;
+
+        (fetched_at.elapsed() < self.ttl).then_some(*price)
+    }
+
+    pub fn insert(&self, network: &str, token: Address, price: f64) {
+        self.prices.write().unwrap().insert((network.to_string(), token), (Instant::now(), price));
+    }
+}
+
+impl Default for PriceCache {
+    fn default() -> Self {
+        Self::new(Duration::from_secs(30))
+    }
+}
@@ -0,0 +1,74 @@
+// Per-network RPC rate limiting for CryptoQuest Arbitrage Bot
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex, RwLock};
+use tokio::time::{Duration, Instant};
+
+// Token bucket refilled at `rate` tokens per second, holding at most one second's worth
+#[derive(Debug)]
+struct TokenBucket {
+    rate: f64,
+    state: Mutex<(f64, Instant)>,
+}
+
+impl TokenBucket {
+    fn new(requests_per_second: u32) -> Self {
+        let rate = f64::from(requests_per_second);
+        Self { rate, state: Mutex::new((rate, Instant::now())) }
+    }
+
+    fn try_take(&self) -> Result<(), Duration> {
+        // Takes a token, or says how long until one is available
+        let mut state = self.state.lock().unwrap();
+        let (tokens, refilled_at) = &mut *state;
+        let now = Instant::now();
+        *tokens = (*tokens + now.duration_since(*refilled_at).as_secs_f64() * self.rate).min(self.rate);
+        *refilled_at = now;
+
+        if *tokens >= 1.0 {
+            *tokens -= 1.0;
+            Ok(())
+        } else {
+            Err(Duration::from_secs_f64((1.0 - *tokens) / self.rate))
+        }
+    }
+}
+
+// Networks without a configured limit are never throttled
+#[derive(Debug, Default)]
+pub struct RateLimiter {
+    buckets: RwLock<HashMap<String, Arc<TokenBucket>>>,
+}
+
+impl RateLimiter {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn set_rate_limit(&self, network: &str, requests_per_second: u32) {
+        // Zero removes the limit
+        let mut buckets = self.buckets.write().unwrap();
+        if requests_per_second == 0 {
+            buckets.remove(network);
+        } else {
+            buckets.insert(network.to_string(), Arc::new(TokenBucket::new(requests_per_second)));
+        }
+    }
+
+    pub fn limits(&self) -> HashMap<String, u32> {
+        // Configured requests per second by network
+        self.buckets.read().unwrap().iter()
+            .map(|(network, bucket)| (network.clone(), bucket.rate as u32))
+            .collect()
+    }
+
+    pub async fn acquire(&self, network: &str) {
+        // Waits for a token rather than failing, so bursts are spread out instead of dropped
+        let Some(bucket) = self.buckets.read().unwrap().get(network).cloned() else {
+            return;
+        };
+
+        while let Err(wait) = bucket.try_take() {
+            tokio::time::sleep(wait).await;
+        }
+    }
+}
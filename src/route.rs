@@ -0,0 +1,115 @@
+// Multi-hop arbitrage routes for CryptoQuest Arbitrage Bot
+use serde::{Deserialize, Serialize};
+
+use crate::{PoolInfo, SwapAction, FEE_TIER_DENOMINATOR};
+
+// Longest cycle searched for, and the default max_hops; triangular routes only for now
+pub const MAX_ROUTE_LENGTH: usize = 3;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ArbitrageRoute {
+    // Pools in execution order, with the swap direction taken on each
+    pub pools: Vec<PoolInfo>,
+    pub actions: Vec<SwapAction>,
+    // The token the route starts and ends in
+    pub start_token: String,
+    // Tokens received per token put in after fees; above 1.0 the cycle is profitable
+    pub rate: f64,
+}
+
+impl ArbitrageRoute {
+    pub fn is_single_network(&self) -> bool {
+        self.bridge_count() == 0
+    }
+
+    pub fn hop_count(&self) -> usize {
+        self.pools.len()
+    }
+
+    pub fn bridge_count(&self) -> usize {
+        // One bridge wherever consecutive hops are on different networks
+        self.pools.windows(2).filter(|pair| pair[0].network != pair[1].network).count()
+    }
+
+    pub fn token_in(&self, hop: usize) -> &str {
+        let pool = &self.pools[hop];
+        match self.actions[hop] {
+            SwapAction::Sell => &pool.token0,
+            SwapAction::Buy => &pool.token1,
+        }
+    }
+
+    pub fn token_out(&self, hop: usize) -> &str {
+        let pool = &self.pools[hop];
+        match self.actions[hop] {
+            SwapAction::Sell => &pool.token1,
+            SwapAction::Buy => &pool.token0,
+        }
+    }
+}
+
+fn hop_from<'a>(pool: &'a PoolInfo, token_in: &str) -> Option<(SwapAction, &'a str, f64)> {
+    // Swap direction, output token and fee-adjusted rate when entering the pool with token_in
+    let fee = 1.0 - pool.fee_tier as f64 / FEE_TIER_DENOMINATOR;
+    if pool.price <= 0.0 {
+        return None;
+    }
+
+    if pool.token0 == token_in {
+        Some((SwapAction::Sell, &pool.token1, pool.price * fee))
+    } else if pool.token1 == token_in {
+        Some((SwapAction::Buy, &pool.token0, fee / pool.price))
+    } else {
+        None
+    }
+}
+
+pub(crate) fn find_cycles(pools: &[PoolInfo]) -> Vec<ArbitrageRoute> {
+    // Each cycle is reported once, starting at its lowest-index pool, in each direction it is profitable
+    let mut routes = Vec::new();
+
+    for (i, first) in pools.iter().enumerate() {
+        for start_token in [&first.token0, &first.token1] {
+            let Some((first_action, token_a, first_rate)) = hop_from(first, start_token) else {
+                continue;
+            };
+
+            for (j, second) in pools.iter().enumerate().skip(i + 1) {
+                if second.network != first.network {
+                    continue;
+                }
+                let Some((second_action, token_b, second_rate)) = hop_from(second, token_a) else {
+                    continue;
+                };
+                if token_b == start_token.as_str() {
+                    continue;
+                }
+
+                for (k, third) in pools.iter().enumerate().skip(i + 1) {
+                    if k == j || third.network != first.network {
+                        continue;
+                    }
+                    let Some((third_action, token_end, third_rate)) = hop_from(third, token_b) else {
+                        continue;
+                    };
+                    if token_end != start_token.as_str() {
+                        continue;
+                    }
+
+                    let rate = first_rate * second_rate * third_rate;
+                    if rate > 1.0 {
+                        routes.push(ArbitrageRoute {
+                            pools: vec![first.clone(), second.clone(), third.clone()],
+                            actions: vec![first_action, second_action, third_action],
+                            start_token: start_token.clone(),
+                            rate,
+                        });
+                    }
+                }
+            }
+        }
+    }
+
+    routes.sort_by(|a, b| b.rate.total_cmp(&a.rate));
+    routes
+}
@@ -8,11 +8,29 @@ import ctypes
 import json
 import os
 import logging
+import time
 from typing import Dict, List, Optional, Tuple
 from dataclasses import dataclass, asdict
 
 logger = logging.getLogger(__name__)
 
+# error_code values reported by the Rust FFI functions
+FFI_OK = 0
+FFI_ERR_PARSE = 1
+FFI_ERR_RUNTIME = 2
+FFI_ERR_REJECTED = 3
+FFI_ERR_NULL_POINTER = 4
+FFI_ERR_LENGTH_MISMATCH = 5
+
+FFI_ERROR_NAMES = {
+    FFI_OK: "ok",
+    FFI_ERR_PARSE: "parse error",
+    FFI_ERR_RUNTIME: "runtime error",
+    FFI_ERR_REJECTED: "rejected by validation",
+    FFI_ERR_NULL_POINTER: "null pointer",
+    FFI_ERR_LENGTH_MISMATCH: "result buffer length mismatch",
+}
+
 @dataclass
 class RustArbitrageOpportunity:
     source_pool: Dict
@@ -44,9 +62,10 @@ class RustArbitrageCore:
         polygon_rpc_c = ctypes.c_char_p(polygon_rpc.encode('utf-8'))
         base_rpc_c = ctypes.c_char_p(base_rpc.encode('utf-8'))
         
-        self.core = self.lib.create_arbitrage_core(polygon_rpc_c, base_rpc_c)
+        error_code = ctypes.c_int32(FFI_OK)
+        self.core = self.lib.create_arbitrage_core(polygon_rpc_c, base_rpc_c, ctypes.byref(error_code))
         if not self.core:
-            raise RuntimeError("Failed to initialize Rust arbitrage core")
+            raise RuntimeError(f"Failed to initialize Rust arbitrage core: {FFI_ERROR_NAMES.get(error_code.value, error_code.value)}")
         
         logger.info("Rust arbitrage core initialized successfully")
     
@@ -72,22 +91,50 @@ class RustArbitrageCore:
         """Define C function signatures for FFI"""
         
         # create_arbitrage_core
-        self.lib.create_arbitrage_core.argtypes = [ctypes.c_char_p, ctypes.c_char_p]
+        self.lib.create_arbitrage_core.argtypes = [ctypes.c_char_p, ctypes.c_char_p, ctypes.POINTER(ctypes.c_int32)]
         self.lib.create_arbitrage_core.restype = ctypes.c_void_p
         
         # validate_opportunity_ffi
-        self.lib.validate_opportunity_ffi.argtypes = [ctypes.c_void_p, ctypes.c_char_p]
+        self.lib.validate_opportunity_ffi.argtypes = [ctypes.c_void_p, ctypes.c_char_p, ctypes.POINTER(ctypes.c_int32)]
         self.lib.validate_opportunity_ffi.restype = ctypes.c_bool
         
+        # validate_opportunities_batch_ffi
+        self.lib.validate_opportunities_batch_ffi.argtypes = [
+            ctypes.c_void_p, ctypes.c_char_p, ctypes.POINTER(ctypes.c_bool), ctypes.c_size_t, ctypes.POINTER(ctypes.c_int32)
+        ]
+        self.lib.validate_opportunities_batch_ffi.restype = ctypes.c_int64
+        
+        # scan_opportunities_ffi returns an owned string; c_void_p keeps the pointer for free_string_ffi
+        self.lib.scan_opportunities_ffi.argtypes = [ctypes.c_void_p, ctypes.c_char_p, ctypes.POINTER(ctypes.c_int32)]
+        self.lib.scan_opportunities_ffi.restype = ctypes.c_void_p
+        
+        # free_string_ffi
+        self.lib.free_string_ffi.argtypes = [ctypes.c_void_p]
+        self.lib.free_string_ffi.restype = None
+        
         # calculate_optimal_amount_ffi
         self.lib.calculate_optimal_amount_ffi.argtypes = [
             ctypes.c_void_p, ctypes.c_uint64, ctypes.c_uint64, ctypes.c_double
         ]
         self.lib.calculate_optimal_amount_ffi.restype = ctypes.c_double
         
+        # calculate_optimal_amount_detailed_ffi returns an owned JSON string, like scan_opportunities_ffi
+        self.lib.calculate_optimal_amount_detailed_ffi.argtypes = [
+            ctypes.c_void_p, ctypes.c_uint64, ctypes.c_uint64, ctypes.c_double, ctypes.POINTER(ctypes.c_int32)
+        ]
+        self.lib.calculate_optimal_amount_detailed_ffi.restype = ctypes.c_void_p
+        
         # free_arbitrage_core
         self.lib.free_arbitrage_core.argtypes = [ctypes.c_void_p]
         self.lib.free_arbitrage_core.restype = None
+        
+        # set_ffi_logging_ffi
+        self.lib.set_ffi_logging_ffi.argtypes = [ctypes.c_bool]
+        self.lib.set_ffi_logging_ffi.restype = None
+    
+    def set_ffi_logging(self, enabled: bool):
+        """Log every FFI call and its result on the Rust side (also enabled by CQT_FFI_LOG)"""
+        self.lib.set_ffi_logging_ffi(ctypes.c_bool(enabled))
     
     def validate_opportunity(self, opportunity: Dict) -> bool:
         """Validate arbitrage opportunity using Rust security checks"""
@@ -98,8 +145,11 @@ class RustArbitrageCore:
             opportunity_c = ctypes.c_char_p(opportunity_json)
             
             # Call Rust function
-            result = self.lib.validate_opportunity_ffi(self.core, opportunity_c)
+            error_code = ctypes.c_int32(FFI_OK)
+            result = self.lib.validate_opportunity_ffi(self.core, opportunity_c, ctypes.byref(error_code))
             
+            if error_code.value not in (FFI_OK, FFI_ERR_REJECTED):
+                logger.error(f"Rust validation failed: {FFI_ERROR_NAMES.get(error_code.value, error_code.value)}")
             logger.debug(f"Opportunity validation result: {result}")
             return bool(result)
             
@@ -107,6 +157,50 @@ class RustArbitrageCore:
             logger.error(f"Error validating opportunity: {e}")
             return False
     
+    def validate_opportunities(self, opportunities: List[Dict]) -> List[bool]:
+        """Validate many opportunities with a single FFI call"""
+        
+        try:
+            opportunities_c = ctypes.c_char_p(json.dumps(opportunities).encode('utf-8'))
+            results = (ctypes.c_bool * len(opportunities))()
+            error_code = ctypes.c_int32(FFI_OK)
+            
+            written = self.lib.validate_opportunities_batch_ffi(
+                self.core, opportunities_c, results, ctypes.c_size_t(len(opportunities)), ctypes.byref(error_code)
+            )
+            if written < 0:
+                logger.error(f"Rust batch validation failed: {FFI_ERROR_NAMES.get(error_code.value, error_code.value)}")
+                return [False] * len(opportunities)
+            
+            return [bool(result) for result in results]
+            
+        except Exception as e:
+            logger.error(f"Error validating opportunities: {e}")
+            return [False] * len(opportunities)
+    
+    def scan_opportunities(self, pools: List[Dict]) -> List[Dict]:
+        """Find validated cross-network opportunities among the given pools using Rust"""
+        
+        try:
+            pools_c = ctypes.c_char_p(json.dumps(pools).encode('utf-8'))
+            error_code = ctypes.c_int32(FFI_OK)
+            result_ptr = self.lib.scan_opportunities_ffi(self.core, pools_c, ctypes.byref(error_code))
+            if not result_ptr:
+                logger.error(f"Rust scan failed: {FFI_ERROR_NAMES.get(error_code.value, error_code.value)}")
+                return []
+            
+            try:
+                opportunities = json.loads(ctypes.string_at(result_ptr).decode('utf-8'))
+            finally:
+                self.lib.free_string_ffi(result_ptr)
+            
+            logger.debug(f"Rust scan found {len(opportunities)} opportunities")
+            return opportunities
+            
+        except Exception as e:
+            logger.error(f"Error scanning opportunities: {e}")
+            return []
+    
     def calculate_optimal_amount(self, source_liquidity: int, target_liquidity: int, price_diff: float) -> float:
         """Calculate optimal arbitrage amount using Rust algorithms"""
         
@@ -125,6 +219,36 @@ class RustArbitrageCore:
             logger.error(f"Error calculating optimal amount: {e}")
             return 0.0
     
+    def calculate_optimal_amount_detailed(self, source_liquidity: int, target_liquidity: int, price_diff: float) -> Dict:
+        """Optimal amount with how it was sized: binding_liquidity, price_multiplier and clamped"""
+        
+        try:
+            error_code = ctypes.c_int32(FFI_OK)
+            result_ptr = self.lib.calculate_optimal_amount_detailed_ffi(
+                self.core,
+                ctypes.c_uint64(source_liquidity),
+                ctypes.c_uint64(target_liquidity),
+                ctypes.c_double(price_diff),
+                ctypes.byref(error_code)
+            )
+            if not result_ptr:
+                logger.error(f"Rust sizing failed: {FFI_ERROR_NAMES.get(error_code.value, error_code.value)}")
+                return {}
+            
+            try:
+                sizing = json.loads(ctypes.string_at(result_ptr).decode('utf-8'))
+            finally:
+                self.lib.free_string_ffi(result_ptr)
+            
+            # U256 amounts cross the FFI as hex strings
+            sizing['amount'] = int(sizing['amount'], 16)
+            logger.debug(f"Optimal amount sizing: {sizing}")
+            return sizing
+            
+        except Exception as e:
+            logger.error(f"Error calculating optimal amount: {e}")
+            return {}
+    
     def calculate_price_impact(self, amount: float, liquidity: float) -> float:
         """Calculate price impact for given trade size"""
         
@@ -327,7 +451,7 @@ def test_rust_integration() -> bool:
             "execution_cost": 0.05,
             "net_profit": 9.8,
             "confidence": 0.95,
-            "timestamp": 1640995200
+            "timestamp": int(time.time())
         }
         
         # Test validation
@@ -0,0 +1,220 @@
+// Transaction signing for CryptoQuest Arbitrage Bot
+use async_trait::async_trait;
+use rlp::RlpStream;
+use std::fmt;
+use std::path::{Path, PathBuf};
+use web3::signing::{keccak256, Key, SecretKey, SecretKeyRef, Signature};
+use web3::types::{Address, H256, U256};
+use zeroize::Zeroizing;
+
+use crate::{ArbitrageError, SecureTransaction};
+
+// Signed transaction bytes; Debug never prints the payload
+#[derive(Clone, PartialEq, Eq)]
+pub struct RawTransaction(pub Vec<u8>);
+
+impl fmt::Debug for RawTransaction {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "RawTransaction({} bytes)", self.0.len())
+    }
+}
+
+// Unsigned transaction payload for signers outside the bot, such as a multisig or remote
+// signing service
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UnsignedTransaction(pub Vec<u8>);
+
+impl UnsignedTransaction {
+    pub fn tx_hash_to_sign(&self) -> [u8; 32] {
+        // Both envelopes sign the hash of exactly these bytes
+        keccak256(&self.0)
+    }
+}
+
+#[async_trait]
+pub trait Signer: Send + Sync {
+    // Account the signed transactions are sent from
+    fn address(&self) -> Address;
+
+    // Raw signed transaction, ready for eth_sendRawTransaction
+    async fn sign(&self, tx: &SecureTransaction, chain_id: u64) -> Result<Vec<u8>, ArbitrageError>;
+
+    // Signature over a 32-byte EIP-712 digest, v being 27 or 28, as permits need. Signers
+    // that can't sign raw digests keep this default and approve on chain instead.
+    async fn sign_digest(&self, _digest: H256) -> Result<Signature, ArbitrageError> {
+        Err(ArbitrageError::Signing("signer cannot sign permit digests".to_string()))
+    }
+}
+
+// Signs with the key in an encrypted JSON (V3) keystore. The key is decrypted for each
+// signature and wiped straight after, so it is never held in memory between trades.
+pub struct LocalSigner {
+    keystore: PathBuf,
+    password: Zeroizing<String>,
+    address: Address,
+}
+
+impl LocalSigner {
+    pub async fn from_keystore(keystore: impl Into<PathBuf>, password: String) -> Result<Self, ArbitrageError> {
+        // Decrypt once up front, both to check the password and to learn the account address
+        let keystore = keystore.into();
+        let password = Zeroizing::new(password);
+        let address = with_secret_key(&keystore, &password, |key| Ok(SecretKeyRef::new(key).address())).await?;
+
+        Ok(Self { keystore, password, address })
+    }
+}
+
+impl fmt::Debug for LocalSigner {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("LocalSigner")
+            .field("keystore", &self.keystore)
+            .field("address", &self.address)
+            .finish_non_exhaustive()
+    }
+}
+
+#[async_trait]
+impl Signer for LocalSigner {
+    fn address(&self) -> Address {
+        self.address
+    }
+
+    async fn sign(&self, tx: &SecureTransaction, chain_id: u64) -> Result<Vec<u8>, ArbitrageError> {
+        let tx = tx.clone();
+        with_secret_key(&self.keystore, &self.password, move |key| {
+            encode_signed_transaction(&tx, chain_id, SecretKeyRef::new(key))
+        }).await
+    }
+
+    async fn sign_digest(&self, digest: H256) -> Result<Signature, ArbitrageError> {
+        with_secret_key(&self.keystore, &self.password, move |key| {
+            SecretKeyRef::new(key).sign(digest.as_bytes(), None)
+                .map_err(|err| ArbitrageError::Signing(err.to_string()))
+        }).await
+    }
+}
+
+async fn with_secret_key<T, F>(keystore: &Path, password: &Zeroizing<String>, f: F) -> Result<T, ArbitrageError>
+where
+    F: FnOnce(&SecretKey) -> Result<T, ArbitrageError> + Send + 'static,
+    T: Send + 'static,
+{
+    // scrypt makes decryption slow, so it runs off the async workers
+    let keystore = keystore.to_path_buf();
+    let password = password.clone();
+
+    tokio::task::spawn_blocking(move || {
+        let key_bytes = Zeroizing::new(
+            eth_keystore::decrypt_key(&keystore, password.as_bytes())
+                .map_err(|err| ArbitrageError::Signing(format!("cannot decrypt {}: {}", keystore.display(), err)))?
+        );
+        let mut key = SecretKey::from_slice(&key_bytes)
+            .map_err(|_| ArbitrageError::Signing(format!("{} does not hold a valid secp256k1 key", keystore.display())))?;
+
+        let result = f(&key);
+        key.non_secure_erase();
+        result
+    })
+    .await
+    .map_err(|err| ArbitrageError::Signing(err.to_string()))?
+}
+
+pub fn encode_unsigned_transaction(tx: &SecureTransaction, chain_id: u64) -> Result<UnsignedTransaction, ArbitrageError> {
+    // EIP-155 legacy or EIP-1559 type-2 envelope, following tx.tx_type
+    match tx.tx_type {
+        0 => Ok(UnsignedTransaction(encode_legacy(tx, chain_id, None))),
+        2 => Ok(UnsignedTransaction(encode_eip1559(tx, chain_id, None))),
+        other => Err(ArbitrageError::Signing(format!("unsupported transaction type {}", other))),
+    }
+}
+
+pub fn encode_signed_transaction(tx: &SecureTransaction, chain_id: u64, key: impl Key) -> Result<Vec<u8>, ArbitrageError> {
+    let hash = encode_unsigned_transaction(tx, chain_id)?.tx_hash_to_sign();
+    let signing_error = |err: web3::signing::SigningError| ArbitrageError::Signing(err.to_string());
+
+    // Legacy v carries the EIP-155 chain id; typed transactions use the bare y-parity
+    if tx.tx_type == 0 {
+        let signature = key.sign(&hash, Some(chain_id)).map_err(signing_error)?;
+        Ok(encode_legacy(tx, chain_id, Some(&signature)))
+    } else {
+        let signature = key.sign_message(&hash).map_err(signing_error)?;
+        Ok(encode_eip1559(tx, chain_id, Some(&signature)))
+    }
+}
+
+fn encode_legacy(tx: &SecureTransaction, chain_id: u64, signature: Option<&Signature>) -> Vec<u8> {
+    let mut stream = RlpStream::new_list(9);
+    stream.append(&tx.nonce)
+        .append(&tx.gas_price)
+        .append(&tx.gas_limit)
+        .append(&tx.to)
+        .append(&tx.value)
+        .append(&tx.data);
+
+    match signature {
+        Some(signature) => append_signature(&mut stream, signature),
+        // EIP-155: the unsigned payload commits to the chain id
+        None => {
+            stream.append(&chain_id).append(&0u8).append(&0u8);
+        }
+    }
+
+    stream.out().to_vec()
+}
+
+pub(crate) fn encode_eip1559(tx: &SecureTransaction, chain_id: u64, signature: Option<&Signature>) -> Vec<u8> {
+    let mut stream = RlpStream::new_list(if signature.is_some() { 12 } else { 9 });
+    stream.append(&chain_id)
+        .append(&tx.nonce)
+        .append(&tx.max_priority_fee_per_gas.unwrap_or_default())
+        .append(&tx.max_fee_per_gas.unwrap_or(tx.gas_price))
+        .append(&tx.gas_limit)
+        .append(&tx.to)
+        .append(&tx.value)
+        .append(&tx.data);
+    // Empty access list
+    stream.begin_list(0);
+
+    if let Some(signature) = signature {
+        append_signature(&mut stream, signature);
+    }
+
+    [&[2u8][..], &stream.out()[..]].concat()
+}
+
+pub(crate) fn signed_transaction_chain_id(raw: &[u8]) -> Result<(u8, u64), ArbitrageError> {
+    // Transaction type and chain id of a signed legacy or EIP-1559 transaction
+    let malformed = |what: &str| ArbitrageError::ValidationFailed(format!("signed transaction {}", what));
+    let decode_error = |err: rlp::DecoderError| malformed(&format!("is not valid RLP: {}", err));
+
+    match raw.first() {
+        // Legacy: an RLP list whose v is chain_id * 2 + 35 or 36 (EIP-155)
+        Some(prefix) if *prefix >= 0xc0 => {
+            let fields = rlp::Rlp::new(raw);
+            if fields.item_count().map_err(decode_error)? != 9 {
+                return Err(malformed("is not a legacy transaction"));
+            }
+            let v: u64 = fields.val_at(6).map_err(decode_error)?;
+            if v < 35 {
+                return Err(malformed("has no EIP-155 chain id"));
+            }
+            Ok((0, (v - 35) / 2))
+        }
+        Some(2) => {
+            let fields = rlp::Rlp::new(&raw[1..]);
+            if fields.item_count().map_err(decode_error)? != 12 {
+                return Err(malformed("is not a signed EIP-1559 transaction"));
+            }
+            Ok((2, fields.val_at(0).map_err(decode_error)?))
+        }
+        Some(other) => Err(malformed(&format!("has unsupported type {}", other))),
+        None => Err(malformed("is empty")),
+    }
+}
+
+fn append_signature(stream: &mut RlpStream, signature: &Signature) {
+    stream.append(&signature.v)
+        .append(&U256::from_big_endian(signature.r.as_bytes()))
+        .append(&U256::from_big_endian(signature.s.as_bytes()));
+}
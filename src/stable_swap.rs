@@ -0,0 +1,85 @@
+// StableSwap (Curve) invariant math for CryptoQuest Arbitrage Bot
+//
+// Two-coin pools with amplification `amp`, in whole token units:
+//   Ann * (x + y) + D = Ann * D + D^3 / (4 * x * y), with Ann = amp * 2
+use crate::FEE_TIER_DENOMINATOR;
+
+const N_COINS: f64 = 2.0;
+
+// Newton iterations stop at this relative change, or after MAX_ITERATIONS as in the contract
+const CONVERGENCE: f64 = 1e-12;
+const MAX_ITERATIONS: usize = 255;
+
+fn invariant(x: f64, y: f64, amp: u64) -> f64 {
+    // get_D: Newton's method starting from the sum of balances
+    let sum = x + y;
+    if sum <= 0.0 {
+        return 0.0;
+    }
+
+    let ann = amp as f64 * N_COINS;
+    let mut d = sum;
+    for _ in 0..MAX_ITERATIONS {
+        let d_p = d * d / (x * N_COINS) * d / (y * N_COINS);
+        let previous = d;
+        d = (ann * sum + d_p * N_COINS) * d / ((ann - 1.0) * d + (N_COINS + 1.0) * d_p);
+        if (d - previous).abs() <= previous * CONVERGENCE {
+            break;
+        }
+    }
+
+    d
+}
+
+fn balance_out(x: f64, d: f64, amp: u64) -> f64 {
+    // get_y: the output-side balance that keeps D fixed once the input side holds x
+    let ann = amp as f64 * N_COINS;
+    let c = d * d / (x * N_COINS) * d / (ann * N_COINS);
+    let b = x + d / ann;
+
+    let mut y = d;
+    for _ in 0..MAX_ITERATIONS {
+        let previous = y;
+        y = (y * y + c) / (2.0 * y + b - d);
+        if (y - previous).abs() <= previous * CONVERGENCE {
+            break;
+        }
+    }
+
+    y
+}
+
+pub(crate) fn get_dy(amount_in: f64, reserve_in: f64, reserve_out: f64, amp: u64, fee_tier: u32) -> f64 {
+    // Output for amount_in, net of the pool fee taken from the output as Curve does
+    if amount_in <= 0.0 || reserve_in <= 0.0 || reserve_out <= 0.0 || amp == 0 {
+        return 0.0;
+    }
+
+    let d = invariant(reserve_in, reserve_out, amp);
+    let dy = (reserve_out - balance_out(reserve_in + amount_in, d, amp)).max(0.0);
+
+    dy * (FEE_TIER_DENOMINATOR - fee_tier as f64) / FEE_TIER_DENOMINATOR
+}
+
+pub(crate) fn spot_price(reserve_in: f64, reserve_out: f64, amp: u64) -> f64 {
+    // Marginal output per unit input, -dy/dx along the invariant curve
+    let d = invariant(reserve_in, reserve_out, amp);
+    let ann = amp as f64 * N_COINS;
+    let d_cubed = d * d * d;
+
+    (ann + d_cubed / (4.0 * reserve_in * reserve_in * reserve_out))
+        / (ann + d_cubed / (4.0 * reserve_in * reserve_out * reserve_out))
+}
+
+pub(crate) fn price_impact(amount_in: f64, reserve_in: f64, reserve_out: f64, amp: u64, fee_tier: u32) -> f64 {
+    // Same convention as calculate_price_impact_v2: execution price after fee against spot
+    if reserve_in <= 0.0 || reserve_out <= 0.0 || amp == 0 {
+        return 1.0;
+    }
+    if amount_in <= 0.0 {
+        return 0.0;
+    }
+
+    let execution_price = get_dy(amount_in, reserve_in, reserve_out, amp, fee_tier) / amount_in;
+    (1.0 - execution_price / spot_price(reserve_in, reserve_out, amp)).clamp(0.0, 1.0)
+}
@@ -0,0 +1,106 @@
+// Pool update subscriptions for CryptoQuest Arbitrage Bot
+use futures::stream::{self, Stream, StreamExt};
+use std::collections::HashMap;
+use tokio::time::{sleep, Duration};
+use web3::api::SubscriptionStream;
+use web3::transports::WebSocket;
+use web3::types::{Address, FilterBuilder, Log};
+
+use crate::{events, ArbitrageError, PoolCache, PoolInfo};
+
+// Upper bound on the reconnect delay, however many attempts have failed
+const MAX_RECONNECT_DELAY: Duration = Duration::from_secs(30);
+
+struct NetworkSubscription<'a> {
+    network: String,
+    ws_url: String,
+    pools: HashMap<Address, PoolInfo>,
+    logs: Option<SubscriptionStream<WebSocket, Log>>,
+    attempt: u32,
+    base_delay: Duration,
+    cache: &'a PoolCache,
+}
+
+impl NetworkSubscription<'_> {
+    async fn connect(&self) -> Result<SubscriptionStream<WebSocket, Log>, ArbitrageError> {
+        let transport = WebSocket::new(&self.ws_url).await?;
+        let filter = FilterBuilder::default()
+            .address(self.pools.keys().copied().collect())
+            .topics(Some(vec![events::sync_topic(), events::v3_swap_topic()]), None, None, None)
+            .build();
+
+        Ok(web3::Web3::new(transport).eth_subscribe().subscribe_logs(filter).await?)
+    }
+
+    async fn next_update(mut self) -> Option<(PoolInfo, Self)> {
+        loop {
+            let Some(logs) = self.logs.as_mut() else {
+                if self.attempt > 0 {
+                    let backoff = self.base_delay.saturating_mul(2u32.saturating_pow(self.attempt - 1));
+                    sleep(backoff.min(MAX_RECONNECT_DELAY)).await;
+                }
+
+                match self.connect().await {
+                    Ok(logs) => self.logs = Some(logs),
+                    Err(err) => {
+                        tracing::warn!(network = %self.network, attempt = self.attempt, error = %err, "pool subscription failed");
+                        self.attempt = self.attempt.saturating_add(1);
+                    }
+                }
+                continue;
+            };
+
+            match logs.next().await {
+                Some(Ok(log)) => {
+                    self.attempt = 0;
+                    if log.removed == Some(true) {
+                        continue;
+                    }
+
+                    let Some(pool) = self.pools.get(&log.address) else {
+                        continue;
+                    };
+                    if let Some(updated) = events::apply_pool_log(pool, &log) {
+                        self.cache.insert(PoolCache::key(&updated.network, &updated.address), updated.clone());
+                        self.pools.insert(log.address, updated.clone());
+                        return Some((updated, self));
+                    }
+                }
+                Some(Err(err)) => {
+                    tracing::warn!(network = %self.network, error = %err, "pool subscription dropped, reconnecting");
+                    self.logs = None;
+                    self.attempt = 1;
+                }
+                None => {
+                    tracing::warn!(network = %self.network, "pool subscription closed, reconnecting");
+                    self.logs = None;
+                    self.attempt = 1;
+                }
+            }
+        }
+    }
+}
+
+pub(crate) fn pool_updates<'a>(
+    networks: Vec<(String, String, Vec<PoolInfo>)>,
+    base_delay: Duration,
+    cache: &'a PoolCache,
+) -> impl Stream<Item = PoolInfo> + 'a {
+    // One socket per network; updates from all of them are merged as they arrive
+    let subscriptions = networks.into_iter().map(move |(network, ws_url, pools)| {
+        let pools = pools.into_iter()
+            .filter_map(|pool| match pool.address.parse::<Address>() {
+                Ok(address) => Some((address, pool)),
+                Err(_) => {
+                    tracing::warn!(pool = %pool.address, "not subscribing to pool with invalid address");
+                    None
+                }
+            })
+            .collect();
+
+        let subscription = NetworkSubscription { network, ws_url, pools, logs: None, attempt: 0, base_delay, cache };
+        Box::pin(stream::unfold(subscription, NetworkSubscription::next_update))
+    });
+
+    stream::select_all(subscriptions)
+}
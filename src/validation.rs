@@ -0,0 +1,98 @@
+// Opportunity validation outcomes for CryptoQuest Arbitrage Bot
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use web3::types::U256;
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "outcome", content = "reason", rename_all = "snake_case")]
+pub enum ValidationOutcome {
+    Accepted,
+    Rejected(RejectionReason),
+}
+
+impl ValidationOutcome {
+    pub fn is_accepted(&self) -> bool {
+        matches!(self, ValidationOutcome::Accepted)
+    }
+
+    pub fn rejection(&self) -> Option<&RejectionReason> {
+        match self {
+            ValidationOutcome::Accepted => None,
+            ValidationOutcome::Rejected(reason) => Some(reason),
+        }
+    }
+}
+
+// One variant per validation check, carrying the values that failed it
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "check", rename_all = "snake_case")]
+pub enum RejectionReason {
+    Stale { age_secs: u64, max_age_secs: u64 },
+    LowProfit { net_profit: f64, min_net_profit: f64 },
+    LowProfitBps { profit_bps: f64, min_profit_bps: u32 },
+    TradeSize { amount: U256, max_amount: U256 },
+    LowConfidence { confidence: f64, min_confidence: f64 },
+    InvalidAddress { address: String },
+    TokenPairMismatch { source_pair: String, target_pair: String },
+    NetProfitMismatch { supplied: f64, recomputed: f64 },
+    Slippage { price_impact: f64, max_slippage: f64 },
+    SandwichRisk { risk: f64, max_risk: f64 },
+    Competition { pool: String, pending: usize, max_pending: usize },
+}
+
+impl RejectionReason {
+    pub fn label(&self) -> &'static str {
+        // Same labels as the rejection metrics
+        match self {
+            RejectionReason::Stale { .. } => "stale",
+            RejectionReason::LowProfit { .. } => "low_profit",
+            RejectionReason::LowProfitBps { .. } => "low_profit_bps",
+            RejectionReason::TradeSize { .. } => "trade_size",
+            RejectionReason::LowConfidence { .. } => "low_confidence",
+            RejectionReason::InvalidAddress { .. } => "invalid_address",
+            RejectionReason::TokenPairMismatch { .. } => "token_pair_mismatch",
+            RejectionReason::NetProfitMismatch { .. } => "net_profit_mismatch",
+            RejectionReason::Slippage { .. } => "slippage",
+            RejectionReason::SandwichRisk { .. } => "sandwich_risk",
+            RejectionReason::Competition { .. } => "competition",
+        }
+    }
+}
+
+impl fmt::Display for RejectionReason {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            RejectionReason::Stale { age_secs, max_age_secs } => {
+                write!(f, "opportunity is {}s old, limit is {}s", age_secs, max_age_secs)
+            }
+            RejectionReason::LowProfit { net_profit, min_net_profit } => {
+                write!(f, "net profit {} is not above {}", net_profit, min_net_profit)
+            }
+            RejectionReason::LowProfitBps { profit_bps, min_profit_bps } => {
+                write!(f, "net profit is {} bps of the trade, below {} bps", profit_bps, min_profit_bps)
+            }
+            RejectionReason::TradeSize { amount, max_amount } => {
+                write!(f, "trade of {} exceeds the per-trade cap {}", amount, max_amount)
+            }
+            RejectionReason::LowConfidence { confidence, min_confidence } => {
+                write!(f, "confidence {} is below {}", confidence, min_confidence)
+            }
+            RejectionReason::InvalidAddress { address } => write!(f, "invalid pool address {}", address),
+            RejectionReason::TokenPairMismatch { source_pair, target_pair } => {
+                write!(f, "source pool trades {} but target pool trades {}", source_pair, target_pair)
+            }
+            RejectionReason::NetProfitMismatch { supplied, recomputed } => {
+                write!(f, "supplied net profit {} does not match recomputed {} after gas", supplied, recomputed)
+            }
+            RejectionReason::Slippage { price_impact, max_slippage } => {
+                write!(f, "price impact {} exceeds max slippage {}", price_impact, max_slippage)
+            }
+            RejectionReason::SandwichRisk { risk, max_risk } => {
+                write!(f, "sandwich risk {} exceeds {}", risk, max_risk)
+            }
+            RejectionReason::Competition { pool, pending, max_pending } => {
+                write!(f, "{} pending transactions target pool {}, limit is {}", pending, pool, max_pending)
+            }
+        }
+    }
+}
@@ -0,0 +1,75 @@
+// Weighted pool (Balancer) math for CryptoQuest Arbitrage Bot
+//
+// Two-token pools holding a fixed share of value per token, in whole token units:
+//   b0^w0 * b1^w1 = k, with the normalized weights summing to 1
+use crate::FEE_TIER_DENOMINATOR;
+
+pub(crate) fn out_given_in(amount_in: f64, balance_in: f64, weight_in: f64, balance_out: f64, weight_out: f64, fee_tier: u32) -> f64 {
+    // calcOutGivenIn, with the swap fee taken from the input as Balancer does:
+    //   out = balance_out * (1 - (balance_in / (balance_in + amount_in)) ^ (weight_in / weight_out))
+    if amount_in <= 0.0 || balance_in <= 0.0 || balance_out <= 0.0 || weight_in <= 0.0 || weight_out <= 0.0 {
+        return 0.0;
+    }
+
+    let amount_in = amount_in * (FEE_TIER_DENOMINATOR - fee_tier as f64) / FEE_TIER_DENOMINATOR;
+    let base = balance_in / (balance_in + amount_in);
+
+    (balance_out * (1.0 - base.powf(weight_in / weight_out))).max(0.0)
+}
+
+pub(crate) fn spot_price(balance_in: f64, weight_in: f64, balance_out: f64, weight_out: f64) -> f64 {
+    // Marginal output per unit input before fees, the inverse of Balancer's calcSpotPrice
+    (balance_out / weight_out) / (balance_in / weight_in)
+}
+
+pub(crate) fn price_impact(amount_in: f64, balance_in: f64, weight_in: f64, balance_out: f64, weight_out: f64, fee_tier: u32) -> f64 {
+    // Same convention as calculate_price_impact_v2: execution price after fee against spot
+    if balance_in <= 0.0 || balance_out <= 0.0 || weight_in <= 0.0 || weight_out <= 0.0 {
+        return 1.0;
+    }
+    if amount_in <= 0.0 {
+        return 0.0;
+    }
+
+    let execution_price = out_given_in(amount_in, balance_in, weight_in, balance_out, weight_out, fee_tier) / amount_in;
+    (1.0 - execution_price / spot_price(balance_in, weight_in, balance_out, weight_out)).clamp(0.0, 1.0)
+}
+
+pub(crate) fn depth_factor(weight0: f64, weight1: f64) -> f64 {
+    // Small trades move a weighted pool's price (1 + weight_in / weight_out) / 2 times as much
+    // as a 50/50 pool of the same balance, so its usable depth shrinks by the inverse. Taken
+    // in the worse direction, since sizing doesn't know which way the pool is traded
+    let total = weight0 + weight1;
+    if weight0 <= 0.0 || weight1 <= 0.0 || !total.is_finite() {
+        return 0.0;
+    }
+
+    2.0 * weight0.min(weight1) / total
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // An 80/20 pool holding 1,000 of the 80% token and 4,000 of the 20% token, 0.3% fee.
+    // Expected outputs are Balancer's calcOutGivenIn evaluated at 50 significant digits.
+    const FEE_TIER: u32 = 3000;
+
+    #[test]
+    fn out_given_in_matches_balancer_for_an_80_20_pool() {
+        // 10 of the heavy token in: 4000 * (1 - (1000 / 1009.97)^4)
+        let out = out_given_in(10.0, 1000.0, 0.8, 4000.0, 0.2, FEE_TIER);
+        assert!((out - 155.621_884_623_04).abs() < 1e-9, "{}", out);
+
+        // 100 of the light token in: 1000 * (1 - (4000 / 4099.7)^0.25)
+        let out = out_given_in(100.0, 4000.0, 0.2, 1000.0, 0.8, FEE_TIER);
+        assert!((out - 6.135_957_446_157_4).abs() < 1e-9, "{}", out);
+    }
+
+    #[test]
+    fn out_given_in_rejects_empty_inputs() {
+        assert_eq!(out_given_in(0.0, 1000.0, 0.8, 4000.0, 0.2, FEE_TIER), 0.0);
+        assert_eq!(out_given_in(10.0, 0.0, 0.8, 4000.0, 0.2, FEE_TIER), 0.0);
+        assert_eq!(out_given_in(10.0, 1000.0, 0.0, 4000.0, 0.2, FEE_TIER), 0.0);
+    }
+}